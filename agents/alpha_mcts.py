@@ -65,6 +65,7 @@ class AlphaMCTS:
         self.dirichlet_eps = dirichlet_eps
         self.device = torch.device(device)
         self.rng = random.Random(seed)
+        self.np_rng = np.random.default_rng(seed)
 
     @torch.no_grad()
     def _policy_value(self, game: SatellitesGame) -> Tuple[np.ndarray, float]:
@@ -90,7 +91,7 @@ class AlphaMCTS:
             probs = probs / denom
 
         if add_noise and len(legal) > 1:
-            noise = np.random.dirichlet([self.dirichlet_alpha] * len(legal))
+            noise = self.np_rng.dirichlet([self.dirichlet_alpha] * len(legal))
             probs = (1.0 - self.dirichlet_eps) * probs + self.dirichlet_eps * noise
 
         node.priors = {a: float(p) for a, p in zip(legal, probs)}
@@ -153,7 +154,7 @@ class AlphaMCTS:
                 raise ValueError("No legal actions from root state.")
             action = legal[self.rng.randrange(len(legal))]
             return action, {"policy": pi, "root_visits": 0}
-        action_idx = int(np.random.choice(np.arange(self.action_space.size), p=pi))
+        action_idx = int(self.np_rng.choice(self.action_space.size, p=pi))
         action = self.action_space.from_index(action_idx)
         return action, {"policy": pi, "root_visits": int(sum(root.visit_count.values()))}
 
@@ -510,6 +510,9 @@ class SatellitesAdapter:
             sats_key,
             state.winner,
             state.turn_count,
+            state.halfmove_clock,
+            state.turn_actions_taken,
+            state.consecutive_passes,
             len(state.grid),
             grid_checksum,
         )
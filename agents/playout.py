@@ -0,0 +1,190 @@
+from __future__ import annotations
+
+import math
+import random
+from concurrent.futures import ProcessPoolExecutor
+from typing import Any, List, Optional, Sequence, Tuple
+
+from engine import SatellitesGame
+
+
+Action = Any
+
+# Material is units on the board plus captured artefact points.
+SCORE_WEIGHT = 3.0
+UNIT_WEIGHT = 1.0
+
+
+def material_eval(game: SatellitesGame, player: int) -> float:
+    """Material balance from player's perspective."""
+    opp = 1 - player
+    score_diff = game.scores[player] - game.scores[opp]
+    unit_diff = game.get_player_unit_count(player) - game.get_player_unit_count(opp)
+    return SCORE_WEIGHT * score_diff + UNIT_WEIGHT * unit_diff
+
+
+def _nearest_unit_distance(game: SatellitesGame, player: int, cell: int) -> int:
+    units = game.owner_bot_cells[player] | game.owner_tank_cells[player]
+    if not units:
+        return game.num_cells
+    return min(game.distance_by_cell_id[cid][cell] for cid in units)
+
+
+def positional_eval(
+    game: SatellitesGame,
+    player: int,
+    *,
+    score_weight: float = 3.0,
+    bot_weight: float = 1.0,
+    tank_weight: float = 1.5,
+    proximity_weight: float = 0.5,
+    scale: float = 10.0,
+) -> float:
+    """Static evaluation from player's perspective, squashed into (-1, 1).
+
+    Sums weighted score and per-kind material differences plus, for each remaining
+    artefact, how many hops closer player's nearest unit is than the opponent's.
+    Finished games return 1, -1 or 0.
+    """
+    if game.state == "GAME_OVER":
+        if game.winner == -1:
+            return 0.0
+        return 1.0 if game.winner == player else -1.0
+    opp = 1 - player
+    material = game.material()
+    bot_diff = material[player * 2] - material[opp * 2]
+    tank_diff = material[player * 2 + 1] - material[opp * 2 + 1]
+    proximity = 0
+    for art in game.artefacts:
+        aid = game.coord_to_cell_id[art]
+        proximity += _nearest_unit_distance(game, opp, aid) - _nearest_unit_distance(game, player, aid)
+    raw = (
+        score_weight * (game.scores[player] - game.scores[opp])
+        + bot_weight * bot_diff
+        + tank_weight * tank_diff
+        + proximity_weight * proximity
+    )
+    return math.tanh(raw / scale)
+
+
+def action_priors(game: SatellitesGame) -> List[Tuple[Action, float]]:
+    """Each legal action paired with the material_eval change it produces for the side to move."""
+    player = game.turn
+    base = material_eval(game, player)
+    out: List[Tuple[Action, float]] = []
+    for action in game.legal_actions():
+        ok, token, _ = game.apply_action_with_undo(action)
+        if ok:
+            out.append((action, material_eval(game, player) - base))
+        game.undo_action(token)
+    return out
+
+
+def greedy_action(game: SatellitesGame) -> Optional[Action]:
+    """Legal action with the best immediate material_eval for the side to move.
+
+    Ties go to the earliest action in legal_actions() order. Returns None if no action is legal.
+    """
+    best_action = None
+    best_delta = float("-inf")
+    for action, delta in action_priors(game):
+        if delta > best_delta:
+            best_delta = delta
+            best_action = action
+    return best_action
+
+
+def finish_turn_greedy(game: SatellitesGame) -> List[Action]:
+    """Spend the rest of the current perform phase on greedy_action; returns the actions applied.
+
+    Mutates game. Does nothing outside PERFORM_ACTIONS.
+    """
+    applied: List[Action] = []
+    player = game.turn
+    while game.state == "PERFORM_ACTIONS" and game.turn == player:
+        action = greedy_action(game)
+        if action is None or not game.apply_action(action):
+            break
+        applied.append(action)
+    return applied
+
+
+def evaluate_turn(
+    game: SatellitesGame,
+    satellite: int,
+    clockwise: bool,
+    actions: List[Action],
+    perspective: int,
+) -> float:
+    """material_eval from perspective after playing a whole turn plan on a clone of game.
+
+    The plan selects satellite, distributes in the given direction, then applies actions
+    in order. Raises ValueError if any step is illegal when reached.
+    """
+    sim = game.clone()
+    for action in [("select_satellite", satellite), ("set_direction", clockwise), *actions]:
+        if action not in sim.legal_actions() or not sim.apply_action(action):
+            raise ValueError(f"Illegal action in turn plan: {action}")
+    return material_eval(sim, perspective)
+
+
+def playout_greedy(
+    game: SatellitesGame,
+    *,
+    seed: Optional[int] = None,
+    greediness: float = 0.8,
+    max_steps: int = 1000,
+) -> Optional[int]:
+    """Epsilon-greedy playout on a clone of game.
+
+    Each step plays greedy_action with probability greediness, otherwise a uniform
+    random legal action. Returns the winner (-1 for a draw), or None if the game is
+    still running after max_steps. Reproducible for a fixed seed.
+    """
+    rng = random.Random(seed)
+    sim = game.clone()
+    for _ in range(max_steps):
+        if sim.state == "GAME_OVER":
+            break
+        actions = sim.legal_actions()
+        if not actions:
+            break
+        action = None
+        if rng.random() < greediness:
+            action = greedy_action(sim)
+        if action is None:
+            action = actions[rng.randrange(len(actions))]
+        if not sim.apply_action(action):
+            break
+    return sim.winner if sim.state == "GAME_OVER" else None
+
+
+def playout_random(game: SatellitesGame, *, seed: Optional[int] = None, max_steps: int = 1000) -> Optional[int]:
+    """Uniformly random playout on a clone of game; playout_greedy with greediness 0."""
+    return playout_greedy(game, seed=seed, greediness=0.0, max_steps=max_steps)
+
+
+def _playout_random_job(job: Tuple[SatellitesGame, int, int]) -> Optional[int]:
+    game, seed, max_steps = job
+    return playout_random(game, seed=seed, max_steps=max_steps)
+
+
+def playout_random_batch(
+    games: Sequence[SatellitesGame],
+    seeds: Sequence[int],
+    *,
+    max_steps: int = 1000,
+    workers: Optional[int] = None,
+) -> List[Optional[int]]:
+    """playout_random for each (game, seed) pair; results in input order.
+
+    With workers > 1 the playouts run in a process pool. Each result depends only on
+    its game and seed, so it matches the serial run.
+    """
+    if len(games) != len(seeds):
+        raise ValueError(f"Got {len(games)} games but {len(seeds)} seeds")
+    jobs = [(game, seed, max_steps) for game, seed in zip(games, seeds)]
+    if workers is None or workers <= 1:
+        return [_playout_random_job(job) for job in jobs]
+    with ProcessPoolExecutor(max_workers=workers) as pool:
+        return list(pool.map(_playout_random_job, jobs))
@@ -1,11 +1,75 @@
+import enum
+import functools
+import hashlib
 import random
+import struct
+import zlib
 
 # ==========================================
 # PART 1: GAME LOGIC (Headless Engine)
 # ==========================================
 
+class StateFormatError(ValueError):
+    """Raised when a serialized game has the wrong version or a bad checksum."""
+
+
+class InvalidStateError(StateFormatError):
+    """Raised when a game state breaks a rules invariant."""
+
+
+class Phase(enum.IntEnum):
+    """Typed view of SatellitesGame.state."""
+    GAME_OVER = 0
+    CHOOSE_SATELLITE = 1
+    CHOOSE_DIRECTION = 2
+    PERFORM_ACTIONS = 3
+
+
+class ActionType(enum.IntEnum):
+    """Typed view of SatellitesGame.action_type; NONE when no satellite is active."""
+    NONE = 0
+    ADD_TANK = 1
+    ADD_BOT = 2
+    MOVE_TANK = 3
+    MOVE_BOT = 4
+
+
+_ZOBRIST_SEED = b"satellites-zobrist-v1"
+
+
+@functools.lru_cache(maxsize=None)
+def _zobrist_key(*parts):
+    """Stable 64-bit key for a hashed feature, identical across runs and processes."""
+    digest = hashlib.blake2b(repr(parts).encode(), digest_size=8, key=_ZOBRIST_SEED).digest()
+    return int.from_bytes(digest, "big")
+
+
+def rotate_charges(charges, active_idx, to_distribute, direction):
+    """Deposit charges one at a time around the satellite ring, starting next to active_idx.
+
+    direction is +1 (clockwise) or -1. Returns a new list; the input is not modified.
+    """
+    out = list(charges)
+    idx = active_idx
+    while to_distribute > 0:
+        idx = (idx + direction) % len(out)
+        out[idx] += 1
+        to_distribute -= 1
+    return out
+
+
 class SatellitesGame:
-    def __init__(self, headless=False):
+    # Binary format: version byte, payload, big-endian CRC32 of version + payload.
+    FORMAT_VERSION = 11
+    STATE_CODES = ("CHOOSE_SATELLITE", "CHOOSE_DIRECTION", "PERFORM_ACTIONS", "GAME_OVER")
+    SAT_TYPE_CODES = ("move_tank", "move_bot", "add_tank", "add_bot")
+    TIE_POLICIES = ("default", "random")
+    END_REASONS = ("score_limit", "artefacts_exhausted", "turn_limit", "halfmove_limit", "no_charges", "both_passed")
+    SAT_NAMES = {'move_tank': 'Move Tank', 'move_bot': 'Move Bot', 'add_tank': 'Add Tank', 'add_bot': 'Add Bot'}
+    _HEADER = struct.Struct(">hhBBBBBBbbHHHHIBBBBQHHHHHh")
+    _CELL = struct.Struct(">BBh")
+
+    def __init__(self, headless=False, seed=None, artefact_values=None):
         self.headless = headless
         
         # Board Setup
@@ -35,6 +99,15 @@ class SatellitesGame:
         self.artefacts = [(2,1), (2,8), (4,4), (4,7), (6,1), (6,8)]
         for coord in self.artefacts:
             self.is_artefact_cell[self.coord_to_cell_id[coord]] = True
+        # Points per bot for capturing each cell's artefact. The standard rules use 1 everywhere;
+        # artefact_values ({coord: value}, 16-bit ints) overrides individual cells.
+        self.artefact_value = [1] * self.num_cells
+        for coord, value in (artefact_values or {}).items():
+            if coord not in self.coord_to_cell_id:
+                raise ValueError(f"Artefact value for a cell off the board: {coord}")
+            if not isinstance(value, int) or isinstance(value, bool) or not -32768 <= value <= 32767:
+                raise ValueError(f"Artefact value at {coord} must be a 16-bit int, got {value!r}")
+            self.artefact_value[self.coord_to_cell_id[coord]] = value
         for coord in ((0,3), (0,4)):
             self.is_p0_start_cell[self.coord_to_cell_id[coord]] = True
         for coord in ((8,3), (8,4)):
@@ -58,7 +131,8 @@ class SatellitesGame:
             {'type': 'add_tank',  'charges': 0, 'name': 'Add Tank'},
             {'type': 'add_bot',   'charges': 0, 'name': 'Add Bot'},
         ]
-        random.shuffle(self.satellites)
+        # seed fixes the satellite ring order; None draws it from the global random module.
+        (random if seed is None else random.Random(seed)).shuffle(self.satellites)
         
         self.scores = [0, 0]
         self.turn = 0 # Player 0 starts
@@ -83,9 +157,52 @@ class SatellitesGame:
              self.info_message = "Player 1's Turn: Choose a Satellite"
         self.winner = None
         
+        # Unit supply per player; 0 means unlimited (sandbox only, not the real rules).
+        self.UNIT_CAP = 20
+        # Score that wins outright when reached by the side to move.
+        self.WIN_SCORE = 9
+
         # FIX: Turn Limit
+        # turn_count counts full rounds (bumped when play returns to player 0);
+        # ply counts individual turns that have ended.
         self.turn_count = 1
         self.MAX_TURNS = 100
+        self.ply = 0
+
+        # Halfmove clock: atomic actions since the last capture or score.
+        # When MAX_HALFMOVE_CLOCK is set, end_turn declares a draw once it is reached.
+        self.halfmove_clock = 0
+        self.MAX_HALFMOVE_CLOCK = None
+
+        # Cell ids mutated by the most recent action (for renderers and incremental encoders).
+        self.last_changed_cells = []
+
+        # Pass detection: adds/moves made this turn, and consecutive forced passes.
+        self.turn_actions_taken = 0
+        self.consecutive_passes = 0
+
+        # Why the game ended (one of END_REASONS), None while it is running.
+        self.end_reason = None
+
+        # Tie resolution: "default" keeps the standard rules (side to move wins an
+        # artefact tie, early endings draw). "random" picks a winner from tie_seed and
+        # the position, so the same position and seed always resolve the same way.
+        self.TIE_POLICY = "default"
+        self.tie_seed = 0
+
+        # Enemy units destroyed by / lost by each player over the game.
+        self.units_captured = [0, 0]
+        self.units_lost = [0, 0]
+
+    @property
+    def tie_seed(self):
+        return self._tie_seed
+
+    @tie_seed.setter
+    def tie_seed(self, value):
+        if not isinstance(value, int) or not 0 <= value < 2 ** 64:
+            raise ValueError(f"tie_seed must be an int in 0..2**64-1, got {value!r}")
+        self._tie_seed = value
 
     @property
     def grid(self):
@@ -120,6 +237,71 @@ class SatellitesGame:
                 self.owner_bot_cells[owner].add(cid)
         self._cache_dirty = False
 
+    def __repr__(self):
+        """ASCII board plus a status line.
+
+        Stacks show kind and count, uppercase for player 0 and lowercase for player 1
+        (T3 / b1). '*' marks an artefact, '+' / '-' an empty player 0 / player 1 start
+        cell, '.' any other empty cell. Rows are indented half a cell per missing
+        column so neighbors line up as on the hex board.
+        """
+        self._ensure_cache()
+        widest = max(self.row_widths)
+        lines = []
+        for r, width in enumerate(self.row_widths):
+            tokens = []
+            for c in range(width):
+                cid = self.coord_to_cell_id[(r, c)]
+                owner = self.unit_owner[cid]
+                if owner != -1:
+                    token = ('T' if self.unit_kind[cid] == 2 else 'B') + str(self.unit_count[cid])
+                    token = token if owner == 0 else token.lower()
+                elif self.is_artefact_cell[cid]:
+                    token = '*'
+                elif self.is_p0_start_cell[cid]:
+                    token = '+'
+                elif self.is_p1_start_cell[cid]:
+                    token = '-'
+                else:
+                    token = '.'
+                tokens.append(token.ljust(4))
+            lines.append((' ' * 2 * (widest - width) + ''.join(tokens)).rstrip())
+        lines.append(
+            f"turn {self.turn} | scores {self.scores[0]}-{self.scores[1]} | {self.state}"
+            f" | active {self.active_satellite_type()}"
+        )
+        return '\n'.join(lines)
+
+    def _refresh_cells(self, coords):
+        """Bring a clean cell cache up to date after the given coords changed in the grid.
+
+        Keeps per-owner totals current without the full rebuild in _ensure_cache; does
+        nothing while the cache is dirty, since the next rebuild will catch up anyway.
+        """
+        if self._cache_dirty:
+            return
+        for coord in coords:
+            cid = self.coord_to_cell_id[coord]
+            old_owner = self.unit_owner[cid]
+            if old_owner != -1:
+                self.owner_total_units[old_owner] -= self.unit_count[cid]
+                self.owner_bot_cells[old_owner].discard(cid)
+                self.owner_tank_cells[old_owner].discard(cid)
+            unit = self._grid.get(coord)
+            if unit is None:
+                self.unit_owner[cid], self.unit_kind[cid], self.unit_count[cid] = -1, 0, 0
+                continue
+            owner = unit['owner']
+            kind = 2 if unit['type'] == 'tank' else 1
+            self.unit_owner[cid], self.unit_kind[cid], self.unit_count[cid] = owner, kind, unit['count']
+            self.owner_total_units[owner] += unit['count']
+            (self.owner_tank_cells if kind == 2 else self.owner_bot_cells)[owner].add(cid)
+        if __debug__:
+            totals = [0, 0]
+            for unit in self._grid.values():
+                totals[unit['owner']] += unit['count']
+            assert self.owner_total_units == totals, f"patched totals {self.owner_total_units} != {totals}"
+
     def add_unit(self, r, c, owner, u_type, count):
         if (r, c) not in self.grid:
             self.grid[(r, c)] = {'owner': owner, 'type': u_type, 'count': 0}
@@ -149,6 +331,7 @@ class SatellitesGame:
         new.owner_bot_cells = [self.owner_bot_cells[0].copy(), self.owner_bot_cells[1].copy()]
         new.owner_tank_cells = [self.owner_tank_cells[0].copy(), self.owner_tank_cells[1].copy()]
         new.is_artefact_cell = self.is_artefact_cell.copy()
+        new.artefact_value = self.artefact_value.copy()
         new.is_p0_start_cell = self.is_p0_start_cell.copy()
         new.is_p1_start_cell = self.is_p1_start_cell.copy()
 
@@ -168,11 +351,300 @@ class SatellitesGame:
         new.info_message = self.info_message
         new.winner = self.winner
         new.turn_count = self.turn_count
+        new.ply = self.ply
         new.MAX_TURNS = self.MAX_TURNS
+        new.UNIT_CAP = self.UNIT_CAP
+        new.WIN_SCORE = self.WIN_SCORE
+        new.halfmove_clock = self.halfmove_clock
+        new.MAX_HALFMOVE_CLOCK = self.MAX_HALFMOVE_CLOCK
+        new.last_changed_cells = self.last_changed_cells.copy()
+        new.turn_actions_taken = self.turn_actions_taken
+        new.consecutive_passes = self.consecutive_passes
+        new.end_reason = self.end_reason
+        new.TIE_POLICY = self.TIE_POLICY
+        new.tie_seed = self.tie_seed
+        new.units_captured = self.units_captured.copy()
+        new.units_lost = self.units_lost.copy()
         if hasattr(self, 'distribution_direction'):
             new.distribution_direction = self.distribution_direction
         return new
 
+    def mirror_coord(self, coord):
+        """Reflection of (r, c) across the board's vertical axis."""
+        r, c = coord
+        return (r, self.row_widths[r] - 1 - c)
+
+    def mirror_action(self, action):
+        """The action that plays the same role on the mirrored board."""
+        if action[0] == 'add':
+            return ('add',) + self.mirror_coord((action[1], action[2]))
+        if action[0] == 'move':
+            return ('move', self.mirror_coord(action[1]), self.mirror_coord(action[2]), action[3])
+        return action
+
+    def mirror(self):
+        """Copy of the game reflected left to right. Satellites and counters are unchanged.
+
+        Mirroring twice gives back the original board.
+        """
+        new = self.clone()
+        cell_map = [self.coord_to_cell_id[self.mirror_coord(coord)] for coord in self.cell_id_to_coord]
+        new.grid = {self.mirror_coord(coord): unit.copy() for coord, unit in self.grid.items()}
+        new.artefacts = [self.mirror_coord(coord) for coord in self.artefacts]
+        for name in ('is_artefact_cell', 'artefact_value', 'is_p0_start_cell', 'is_p1_start_cell'):
+            src = getattr(self, name)
+            dst = [None] * self.num_cells
+            for cid, mid in enumerate(cell_map):
+                dst[mid] = src[cid]
+            setattr(new, name, dst)
+        new.last_changed_cells = [cell_map[cid] for cid in self.last_changed_cells]
+        new.selected_hex = None if self.selected_hex is None else self.mirror_coord(self.selected_hex)
+        new.pending_move_dest = None if self.pending_move_dest is None else self.mirror_coord(self.pending_move_dest)
+        return new
+
+    def to_bytes(self):
+        """Serialize the rules state (not UI selection state) to a compact byte blob.
+
+        Raises ValueError if a rule setting does not fit its serialized field.
+        """
+        # 0xFFFF in the halfmove limit field means no limit.
+        max_halfmove = 0xFFFF if self.MAX_HALFMOVE_CLOCK is None else self.MAX_HALFMOVE_CLOCK
+        for name, value, low, high in (
+            ("MAX_TURNS", self.MAX_TURNS, 0, 0xFFFF),
+            ("MAX_HALFMOVE_CLOCK", self.MAX_HALFMOVE_CLOCK or 0, 0, 0xFFFE),
+            ("UNIT_CAP", self.UNIT_CAP, 0, 0xFFFF),
+            ("WIN_SCORE", self.WIN_SCORE, -0x8000, 0x7FFF),
+        ):
+            if not low <= value <= high:
+                raise ValueError(f"{name} {value} does not fit in {low}..{high}")
+        self._ensure_cache()
+        direction = getattr(self, 'distribution_direction', 0)
+        header = self._HEADER.pack(
+            self.scores[0],
+            self.scores[1],
+            self.turn,
+            self.STATE_CODES.index(self.state),
+            255 if self.active_satellite_idx is None else self.active_satellite_idx,
+            self.actions_remaining,
+            self.picked_up_charges,
+            255 if self.action_type is None else self.SAT_TYPE_CODES.index(self.action_type),
+            direction,
+            -128 if self.winner is None else self.winner,
+            self.turn_count,
+            self.MAX_TURNS,
+            self.halfmove_clock,
+            max_halfmove,
+            self.ply,
+            self.turn_actions_taken,
+            self.consecutive_passes,
+            255 if self.end_reason is None else self.END_REASONS.index(self.end_reason),
+            self.TIE_POLICIES.index(self.TIE_POLICY),
+            self.tie_seed,
+            self.units_captured[0],
+            self.units_captured[1],
+            self.units_lost[0],
+            self.units_lost[1],
+            self.UNIT_CAP,
+            self.WIN_SCORE,
+        )
+        body = bytearray(header)
+        for sat in self.satellites:
+            body += bytes((self.SAT_TYPE_CODES.index(sat['type']), sat['charges']))
+        for cid in range(self.num_cells):
+            # Code, unit count, artefact value. Code low bits: 0 empty, 1/2 p0 bot/tank,
+            # 3/4 p1 bot/tank. Flag bits: 0x80 artefact, 0x40 p0 start, 0x20 p1 start.
+            code = 0 if self.unit_owner[cid] == -1 else self.unit_owner[cid] * 2 + self.unit_kind[cid]
+            if self.is_artefact_cell[cid]:
+                code |= 0x80
+            if self.is_p0_start_cell[cid]:
+                code |= 0x40
+            if self.is_p1_start_cell[cid]:
+                code |= 0x20
+            body += self._CELL.pack(code, self.unit_count[cid], self.artefact_value[cid])
+        data = bytes((self.FORMAT_VERSION,)) + bytes(body)
+        return data + struct.pack(">I", zlib.crc32(data))
+
+    def fingerprint(self):
+        """Short hex digest of the serialized rules state, for snapshot tests.
+
+        Changes whenever the to_bytes() payload layout changes.
+        """
+        return hashlib.blake2b(self.to_bytes()[1:-4], digest_size=8).hexdigest()
+
+    def zobrist_hash(self):
+        """64-bit Zobrist-style hash of the gameplay state, for transposition tables.
+
+        Covers cell occupants, satellites, remaining artefacts, turn, phase, active
+        satellite, actions remaining, picked-up charges, distribution direction and
+        scores. Not cryptographic.
+        """
+        self._ensure_cache()
+        h = 0
+        for cid in range(self.num_cells):
+            if self.unit_owner[cid] != -1:
+                h ^= _zobrist_key("cell", cid, self.unit_owner[cid], self.unit_kind[cid], self.unit_count[cid])
+            if self.is_artefact_cell[cid]:
+                h ^= _zobrist_key("artefact", cid)
+        for slot, sat in enumerate(self.satellites):
+            h ^= _zobrist_key("sat", slot, sat['type'], sat['charges'])
+        h ^= _zobrist_key(
+            "side", self.turn, self.state, self.action_type, self.active_satellite_idx, self.actions_remaining
+        )
+        h ^= _zobrist_key("charges", self.picked_up_charges, getattr(self, 'distribution_direction', 0))
+        h ^= _zobrist_key("scores", self.scores[0], self.scores[1])
+        return h
+
+    @classmethod
+    def from_bytes(cls, data):
+        """Rebuild a headless game from to_bytes() output, validating version and checksum."""
+        data = bytes(data)
+        if len(data) < 5:
+            raise StateFormatError("Serialized game is truncated")
+        if data[0] != cls.FORMAT_VERSION:
+            raise StateFormatError(f"Unsupported format version {data[0]} (expected {cls.FORMAT_VERSION})")
+        (crc,) = struct.unpack(">I", data[-4:])
+        if zlib.crc32(data[:-4]) != crc:
+            raise StateFormatError("Serialized game checksum mismatch")
+
+        game = cls(headless=True)
+        body = data[1:-4]
+        expected = cls._HEADER.size + 6 * 2 + game.num_cells * cls._CELL.size
+        if len(body) != expected:
+            raise StateFormatError(f"Serialized game has {len(body)} payload bytes, expected {expected}")
+        (
+            s0, s1, turn, state, active, actions_remaining, picked_up, action_type,
+            direction, winner, turn_count, max_turns, halfmove, max_halfmove, ply,
+            turn_actions_taken, consecutive_passes, end_reason, tie_policy, tie_seed,
+            captured0, captured1, lost0, lost1, unit_cap, win_score,
+        ) = cls._HEADER.unpack_from(body, 0)
+        p = cls._HEADER.size
+        for name, code, table, none_code in (
+            ("state", state, cls.STATE_CODES, None),
+            ("action type", action_type, cls.SAT_TYPE_CODES, 255),
+            ("end reason", end_reason, cls.END_REASONS, 255),
+            ("tie policy", tie_policy, cls.TIE_POLICIES, None),
+        ):
+            if code != none_code and code >= len(table):
+                raise StateFormatError(f"Unknown {name} code {code}")
+
+        game.scores = [s0, s1]
+        game.turn = turn
+        game.state = cls.STATE_CODES[state]
+        game.active_satellite_idx = None if active == 255 else active
+        game.actions_remaining = actions_remaining
+        game.picked_up_charges = picked_up
+        game.action_type = None if action_type == 255 else cls.SAT_TYPE_CODES[action_type]
+        if direction:
+            game.distribution_direction = direction
+        game.winner = None if winner == -128 else winner
+        game.turn_count = turn_count
+        game.MAX_TURNS = max_turns
+        game.halfmove_clock = halfmove
+        game.MAX_HALFMOVE_CLOCK = None if max_halfmove == 0xFFFF else max_halfmove
+        game.ply = ply
+        game.turn_actions_taken = turn_actions_taken
+        game.consecutive_passes = consecutive_passes
+        game.end_reason = None if end_reason == 255 else cls.END_REASONS[end_reason]
+        game.TIE_POLICY = cls.TIE_POLICIES[tie_policy]
+        game.tie_seed = tie_seed
+        game.units_captured = [captured0, captured1]
+        game.units_lost = [lost0, lost1]
+        game.UNIT_CAP = unit_cap
+        game.WIN_SCORE = win_score
+
+        game.satellites = []
+        for _ in range(6):
+            if body[p] >= len(cls.SAT_TYPE_CODES):
+                raise StateFormatError(f"Unknown satellite type code {body[p]}")
+            sat_type = cls.SAT_TYPE_CODES[body[p]]
+            game.satellites.append({'type': sat_type, 'charges': body[p + 1], 'name': cls.SAT_NAMES[sat_type]})
+            p += 2
+
+        grid = {}
+        artefacts = []
+        game.is_artefact_cell = [False] * game.num_cells
+        game.is_p0_start_cell = [False] * game.num_cells
+        game.is_p1_start_cell = [False] * game.num_cells
+        for cid in range(game.num_cells):
+            code, count, game.artefact_value[cid] = cls._CELL.unpack_from(body, p)
+            p += cls._CELL.size
+            coord = game.cell_id_to_coord[cid]
+            if code & 0x80:
+                artefacts.append(coord)
+                game.is_artefact_cell[cid] = True
+            game.is_p0_start_cell[cid] = bool(code & 0x40)
+            game.is_p1_start_cell[cid] = bool(code & 0x20)
+            unit = code & 0x1F
+            if unit:
+                owner, kind = divmod(unit - 1, 2)
+                grid[coord] = {'owner': owner, 'type': 'tank' if kind == 1 else 'bot', 'count': count}
+        game.grid = grid
+        game.artefacts = artefacts
+        game.info_message = ""
+        game.validate()
+        return game
+
+    def validate(self):
+        """Raise InvalidStateError naming the first broken rules invariant, if any."""
+        if self.turn not in (0, 1):
+            raise InvalidStateError(f"turn is {self.turn}")
+        if self.winner not in (None, -1, 0, 1):
+            raise InvalidStateError(f"winner is {self.winner}")
+        if (self.state == "GAME_OVER") != (self.winner is not None):
+            raise InvalidStateError(f"state {self.state} with winner {self.winner}")
+        if self.active_satellite_idx is not None and not 0 <= self.active_satellite_idx < len(self.satellites):
+            raise InvalidStateError(f"active satellite {self.active_satellite_idx} is off the ring")
+        if self.state == "PERFORM_ACTIONS" and self.action_type is None:
+            raise InvalidStateError("perform phase without an action type")
+        if len(self.satellites) != 6:
+            raise InvalidStateError(f"{len(self.satellites)} satellites on the ring")
+        for coord, unit in self.grid.items():
+            cid = self.coord_to_cell_id.get(coord)
+            if cid is None:
+                raise InvalidStateError(f"unit off the board at {coord}")
+            if unit['owner'] not in (0, 1):
+                raise InvalidStateError(f"owner {unit['owner']} at {coord}")
+            if unit['type'] not in ('bot', 'tank'):
+                raise InvalidStateError(f"unit type {unit['type']!r} at {coord}")
+            if unit['count'] <= 0:
+                raise InvalidStateError(f"empty stack at {coord}")
+            if self.is_artefact_cell[cid]:
+                raise InvalidStateError(f"unit on uncaptured artefact {coord}")
+            if self._start_mask(1 - unit['owner'])[cid]:
+                raise InvalidStateError(f"player {unit['owner']} unit on opponent start cell {coord}")
+        if self.UNIT_CAP:
+            for owner in (0, 1):
+                if self.get_player_unit_count(owner) > self.UNIT_CAP:
+                    raise InvalidStateError(f"player {owner} has more than {self.UNIT_CAP} units")
+
+    @classmethod
+    def random_board(cls, num_artefacts, max_turns, seed, headless=True):
+        """Starting position with num_artefacts artefacts on random empty non-start cells.
+
+        Artefact placement and satellite order both come from seed, so the same seed
+        always yields the same board.
+        """
+        rng = random.Random(seed)
+        # A fixed constructor seed keeps the global random module out of it; the ring is
+        # re-sorted and shuffled from rng below anyway.
+        game = cls(headless=headless, seed=0)
+        game._ensure_cache()
+        candidates = [
+            cid for cid in range(game.num_cells)
+            if game.unit_owner[cid] == -1 and not game.is_p0_start_cell[cid] and not game.is_p1_start_cell[cid]
+        ]
+        if not 0 <= num_artefacts <= len(candidates):
+            raise ValueError(f"Cannot place {num_artefacts} artefacts on {len(candidates)} free cells")
+        chosen = sorted(rng.sample(candidates, num_artefacts))
+        game.artefacts = [game.cell_id_to_coord[cid] for cid in chosen]
+        game.is_artefact_cell = [False] * game.num_cells
+        for cid in chosen:
+            game.is_artefact_cell[cid] = True
+        game.satellites.sort(key=lambda sat: cls.SAT_TYPE_CODES.index(sat['type']))
+        rng.shuffle(game.satellites)
+        game.MAX_TURNS = max_turns
+        return game
+
     def _capture_undo_token_for_action(self, action):
         kind = action[0]
         changed_cells = {}
@@ -208,7 +680,20 @@ class SatellitesGame:
             "info_message": self.info_message,
             "winner": self.winner,
             "turn_count": self.turn_count,
+            "ply": self.ply,
             "MAX_TURNS": self.MAX_TURNS,
+            "UNIT_CAP": self.UNIT_CAP,
+            "WIN_SCORE": self.WIN_SCORE,
+            "halfmove_clock": self.halfmove_clock,
+            "MAX_HALFMOVE_CLOCK": self.MAX_HALFMOVE_CLOCK,
+            "last_changed_cells": self.last_changed_cells.copy(),
+            "turn_actions_taken": self.turn_actions_taken,
+            "consecutive_passes": self.consecutive_passes,
+            "end_reason": self.end_reason,
+            "TIE_POLICY": self.TIE_POLICY,
+            "tie_seed": self.tie_seed,
+            "units_captured": self.units_captured.copy(),
+            "units_lost": self.units_lost.copy(),
             "distribution_direction": getattr(self, "distribution_direction", None),
         }
 
@@ -221,7 +706,7 @@ class SatellitesGame:
         self.artefacts = token["artefacts"]
         self.is_artefact_cell = token["is_artefact_cell"]
         self.satellites = token["satellites"]
-        self._cache_dirty = True
+        self._refresh_cells(token["_grid_cells"])
         self.scores = token["scores"]
         self.turn = token["turn"]
         self.state = token["state"]
@@ -236,12 +721,41 @@ class SatellitesGame:
         self.info_message = token["info_message"]
         self.winner = token["winner"]
         self.turn_count = token["turn_count"]
+        self.ply = token["ply"]
         self.MAX_TURNS = token["MAX_TURNS"]
+        self.UNIT_CAP = token["UNIT_CAP"]
+        self.WIN_SCORE = token["WIN_SCORE"]
+        self.halfmove_clock = token["halfmove_clock"]
+        self.MAX_HALFMOVE_CLOCK = token["MAX_HALFMOVE_CLOCK"]
+        self.last_changed_cells = token["last_changed_cells"]
+        self.turn_actions_taken = token["turn_actions_taken"]
+        self.consecutive_passes = token["consecutive_passes"]
+        self.end_reason = token["end_reason"]
+        self.TIE_POLICY = token["TIE_POLICY"]
+        self.tie_seed = token["tie_seed"]
+        self.units_captured = token["units_captured"]
+        self.units_lost = token["units_lost"]
         if token["distribution_direction"] is not None:
             self.distribution_direction = token["distribution_direction"]
         elif hasattr(self, "distribution_direction"):
             delattr(self, "distribution_direction")
 
+    def undo_turn(self, tokens):
+        """Rewind to just before the side to move picked its satellite this turn.
+
+        tokens are apply_action_with_undo tokens, oldest first; the undone ones are
+        popped off the end. Returns False if the current turn has nothing to undo.
+        """
+        turn = self.turn
+        undone = False
+        while tokens and tokens[-1]["turn"] == turn:
+            token = tokens.pop()
+            self.undo_action(token)
+            undone = True
+            if token["state"] == "CHOOSE_SATELLITE":
+                break
+        return undone
+
     def apply_action_with_undo(self, action):
         """Apply an action and return (success, token, aux).
 
@@ -294,11 +808,16 @@ class SatellitesGame:
             return success
         raise ValueError(f"Unsupported action kind: {kind}")
 
+    def with_action(self, action):
+        """(clone with action applied, success), leaving this game untouched."""
+        child = self.clone()
+        return child, child.apply_action(action)
+
     def _is_legal_add(self, r, c):
         if self.state != "PERFORM_ACTIONS" or "add" not in (self.action_type or ""):
             return False
         self._ensure_cache()
-        if self.get_player_unit_count(self.turn) >= 20:
+        if self.at_unit_cap(self.turn):
             return False
 
         unit_type = 'tank' if 'tank' in self.action_type else 'bot'
@@ -316,16 +835,14 @@ class SatellitesGame:
             )
             if occ_owner != -1 and not is_own_tank_stack:
                 return False
-            opp_starts = [(8,3), (8,4)] if self.turn == 0 else [(0,3), (0,4)]
-            if (r, c) in opp_starts:
+            if self._start_mask(1 - self.turn)[cid]:
                 return False
             if (r, c) in self.artefacts:
                 return False
             return True
 
         is_own_stack = (current and current['owner'] == self.turn and current['type'] == unit_type)
-        valid_starts = [(0,3), (0,4)] if self.turn == 0 else [(8,3), (8,4)]
-        is_start_zone = ((r, c) in valid_starts) and (not current or is_own_stack)
+        is_start_zone = self._start_mask(self.turn)[cid] and (not current or is_own_stack)
         return bool(is_own_stack or is_start_zone)
 
     def _is_legal_move(self, start, end, amount):
@@ -349,8 +866,7 @@ class SatellitesGame:
         if end not in self.get_hex_neighbors(start[0], start[1]):
             return False
 
-        opp_starts = [(8,3), (8,4)] if self.turn == 0 else [(0,3), (0,4)]
-        if end in opp_starts:
+        if self._start_mask(1 - self.turn)[eid]:
             return False
 
         move_type = src_kind
@@ -369,29 +885,30 @@ class SatellitesGame:
         return True
 
     def legal_actions(self):
-        if self.state == "GAME_OVER":
-            return []
+        return list(self._iter_legal_actions())
 
+    def _iter_legal_actions(self, max_move_amount=None):
+        """Lazily yield legal_actions() in order, skipping moves above max_move_amount."""
         if self.state == "CHOOSE_SATELLITE":
-            actions = []
             for i, sat in enumerate(self.satellites):
                 if sat['charges'] > 0:
-                    actions.append(('select_satellite', i))
-            return actions
+                    yield ('select_satellite', i)
+            return
 
         if self.state == "CHOOSE_DIRECTION":
-            return [('set_direction', False), ('set_direction', True)]
+            yield ('set_direction', False)
+            yield ('set_direction', True)
+            return
 
         if self.state != "PERFORM_ACTIONS":
-            return []
+            return
 
-        actions = []
         if "add" in (self.action_type or ""):
             for r in range(9):
                 for c in range(self.row_widths[r]):
                     if self._is_legal_add(r, c):
-                        actions.append(('add', r, c))
-            return actions
+                        yield ('add', r, c)
+            return
 
         if "move" in (self.action_type or ""):
             self._ensure_cache()
@@ -402,18 +919,342 @@ class SatellitesGame:
                 unit = self.grid.get((r, c))
                 if not unit:
                     continue
+                max_amount = unit['count'] if max_move_amount is None else min(unit['count'], max_move_amount)
                 for nr, nc in self.get_hex_neighbors(r, c):
-                    for amount in range(1, unit['count'] + 1):
+                    for amount in range(1, max_amount + 1):
                         if self._is_legal_move((r, c), (nr, nc), amount):
-                            actions.append(('move', (r, c), (nr, nc), amount))
-            return actions
+                            yield ('move', (r, c), (nr, nc), amount)
+
+    def sample_legal_action(self, seed=None, max_move_amount=None):
+        """A uniformly random legal action (moves above max_move_amount excluded), or None.
+
+        Single-pass reservoir sample over the lazy generator, so no action list is built;
+        the same state and seed always pick the same action.
+        """
+        rng = random.Random(seed)
+        chosen = None
+        for seen, action in enumerate(self._iter_legal_actions(max_move_amount), 1):
+            if rng.randrange(seen) == 0:
+                chosen = action
+        return chosen
+
+    def all_legal_moves(self, max_move_amount=None):
+        """(kind, start, end, amount) for every tank and bot move the side to move could make,
+        regardless of which satellite is active. kind is 'tank' or 'bot'.
+        """
+        if self.state == "GAME_OVER":
+            return []
+        probe = self.clone()
+        probe.state = "PERFORM_ACTIONS"
+        probe.actions_remaining = 1
+        out = []
+        for kind in ('tank', 'bot'):
+            probe.action_type = f"move_{kind}"
+            out.extend((kind, start, end, amount) for start, end, amount in probe.legal_moves(max_move_amount))
+        return out
+
+    def threatened_cells(self, by_player, max_move_amount=None):
+        """Per cell id, True if a by_player tank move could reach it next (empty or enemy cells).
+
+        Uses the normal tank move rules, including start cell and artefact limits.
+        """
+        mask = [False] * self.num_cells
+        if self.state == "GAME_OVER":
+            return mask
+        probe = self.clone()
+        probe.turn = by_player
+        probe.state = "PERFORM_ACTIONS"
+        probe.action_type = "move_tank"
+        probe.actions_remaining = 1
+        probe._ensure_cache()
+        for _, end, _ in probe.legal_moves(max_move_amount):
+            eid = self.coord_to_cell_id[end]
+            if probe.unit_owner[eid] != by_player:
+                mask[eid] = True
+        return mask
+
+    def max_move_amounts(self, max_move_amount=None):
+        """Per cell id, the largest legal move amount to any neighbor (0 if the cell can't move)."""
+        out = [0] * self.num_cells
+        for start, _, amount in self.legal_moves(max_move_amount):
+            sid = self.coord_to_cell_id[start]
+            if amount > out[sid]:
+                out[sid] = amount
+        return out
+
+    def legal_counts_by_type(self, max_move_amount=None):
+        """Legal (satellite choices, directions, adds, moves) in the current state."""
+        counts = {'select_satellite': 0, 'set_direction': 0, 'add': 0, 'move': 0}
+        for action in self._iter_legal_actions(max_move_amount):
+            counts[action[0]] += 1
+        return (counts['select_satellite'], counts['set_direction'], counts['add'], counts['move'])
+
+    def legal_action_count(self, max_move_amount=None):
+        """Number of legal actions, dropping moves above max_move_amount."""
+        return sum(self.legal_counts_by_type(max_move_amount))
+
+    def try_select_satellite(self, idx):
+        """Pick up satellite idx if a satellite choice is pending and it holds charges. Returns success."""
+        if self.state != "CHOOSE_SATELLITE" or not 0 <= idx < len(self.satellites):
+            return False
+        if self.satellites[idx]['charges'] == 0:
+            return False
+        return self.apply_action(('select_satellite', idx))
+
+    def try_set_direction(self, clockwise):
+        """Distribute the picked-up charges if a direction choice is pending. Returns success."""
+        if self.state != "CHOOSE_DIRECTION":
+            return False
+        return self.apply_action(('set_direction', bool(clockwise)))
+
+    def try_add(self, r, c):
+        """Add one unit at (r, c) if the active satellite allows it. Returns success."""
+        if not self._is_legal_add(r, c):
+            return False
+        return self.execute_add(r, c)
+
+    def try_move(self, start, end, amount):
+        """Move amount units from start to end if the active satellite allows it. Returns success.
+
+        Unlike execute_move, this rejects moves outside a matching move phase.
+        """
+        if not self._is_legal_move(start, end, amount):
+            return False
+        success, _, _ = self.execute_move(start, end, amount)
+        return success
+
+    def legal_add_cells(self):
+        """Cell ids where the current add action is legal, ascending. Empty outside an add phase."""
+        if self.state != "PERFORM_ACTIONS" or "add" not in (self.action_type or ""):
+            return []
+        return [self.coord_to_cell_id[(a[1], a[2])] for a in self._iter_legal_actions()]
+
+    def legal_moves(self, max_move_amount=None):
+        """(start, end, amount) for every legal move in the current perform phase.
+
+        Empty outside a move phase. Amounts above max_move_amount are dropped.
+        """
+        if self.state != "PERFORM_ACTIONS" or "move" not in (self.action_type or ""):
+            return []
+        return [
+            (a[1], a[2], a[3])
+            for a in self.legal_actions()
+            if max_move_amount is None or a[3] <= max_move_amount
+        ]
+
+    def legal_amounts(self, start, end, max_move_amount=None):
+        """Inclusive (lo, hi) of legal move amounts from start to end, or (0, 0) if none."""
+        amounts = [a for s, e, a in self.legal_moves(max_move_amount) if s == start and e == end]
+        if not amounts:
+            return (0, 0)
+        return (min(amounts), max(amounts))
+
+    def scored_moves(self, max_move_amount=None):
+        """legal_moves as (start, end, amount, score_delta), score_delta being the points
+        the move would earn at once by capturing an artefact (0 otherwise).
+        """
+        out = []
+        for start, end, amount in self.legal_moves(max_move_amount):
+            delta = 0
+            if self.move_scores_artefact(start, end, amount):
+                delta = amount * self.artefact_value[self.coord_to_cell_id[end]]
+            out.append((start, end, amount, delta))
+        return out
+
+    def satellites_of_type(self, sat_type, charged_only=False):
+        """Ring slots holding satellites of sat_type (a type string or SAT_TYPE_CODES index)."""
+        if isinstance(sat_type, int):
+            if not 0 <= sat_type < len(self.SAT_TYPE_CODES):
+                raise ValueError(f"Unknown satellite type code: {sat_type}")
+            sat_type = self.SAT_TYPE_CODES[sat_type]
+        elif sat_type not in self.SAT_TYPE_CODES:
+            raise ValueError(f"Unknown satellite type: {sat_type}")
+        return [
+            i for i, sat in enumerate(self.satellites)
+            if sat['type'] == sat_type and (not charged_only or sat['charges'] > 0)
+        ]
+
+    def phase(self):
+        """Current state as a Phase."""
+        return Phase[self.state]
+
+    def action_type_enum(self):
+        """Current action_type as an ActionType."""
+        return ActionType.NONE if self.action_type is None else ActionType[self.action_type.upper()]
+
+    def active_satellite_type(self):
+        """Type of the satellite picked this turn (e.g. 'move_tank'), or None if none is active."""
+        if self.active_satellite_idx is None:
+            return None
+        return self.satellites[self.active_satellite_idx]['type']
+
+    def perform_action_kind(self):
+        """('add' | 'move', 'bot' | 'tank') the active satellite grants, or None if none is active.
+
+        Available from satellite selection on, before the perform phase starts.
+        """
+        sat_type = self.active_satellite_type()
+        if sat_type is None:
+            return None
+        verb, unit = sat_type.split('_')
+        return (verb, unit)
+
+    def total_charges(self):
+        """Charges on the ring, excluding any picked up for the current turn."""
+        return sum(sat['charges'] for sat in self.satellites)
+
+    def charged_satellites(self):
+        """Number of ring slots holding at least one charge."""
+        return sum(1 for sat in self.satellites if sat['charges'] > 0)
+
+    def captures(self, player):
+        """(enemy units destroyed, own units lost) for player so far."""
+        return (self.units_captured[player], self.units_lost[player])
+
+    def turns_remaining(self):
+        """MAX_TURNS - turn_count: full rounds left before the turn limit (0 in the final round)."""
+        return self.MAX_TURNS - self.turn_count
+
+    def get_scores(self):
+        """(player 0 score, player 1 score)."""
+        return (self.scores[0], self.scores[1])
+
+    def can_still_win(self, player):
+        """False once player cannot win even by capturing every remaining artefact.
+
+        Each capture scores at most UNIT_CAP times the cell's artefact value; negative-value
+        artefacts are assumed left alone, and UNIT_CAP == 0 never rules a win out.
+        A finished game answers whether player is the recorded winner.
+        """
+        if self.state == "GAME_OVER":
+            return self.winner == player
+        if self.UNIT_CAP == 0:
+            return True
+        remaining = sum(max(0, self.artefact_value[self.coord_to_cell_id[a]]) for a in self.artefacts)
+        best = self.scores[player] + remaining * self.UNIT_CAP
+        # Reaching WIN_SCORE wins outright; otherwise the artefact count-out needs at least a tie.
+        return best >= self.WIN_SCORE or best >= self.scores[1 - player]
+
+    def board_summary(self):
+        """Snapshot dict of board-level counts for dashboards.
+
+        Keys: empty_cells, stacks (occupied cells) and units per player, artefacts,
+        total_charges, scores.
+        """
+        self._ensure_cache()
+        stacks = (self.unit_owner.count(0), self.unit_owner.count(1))
+        return {
+            "empty_cells": self.num_cells - sum(stacks),
+            "stacks": stacks,
+            "units": tuple(self.owner_total_units),
+            "artefacts": len(self.artefacts),
+            "total_charges": self.total_charges(),
+            "scores": self.get_scores(),
+        }
+
+    def game_result(self):
+        """(winner, reason code) once the game is over, else None.
+
+        winner is -1 for a draw; the reason code indexes END_REASONS.
+        """
+        if self.state != "GAME_OVER":
+            return None
+        return (self.winner, self.END_REASONS.index(self.end_reason))
+
+    def final_result(self):
+        """Summary dict of a finished game, or None while it is still running.
+
+        Keys: winner (-1 for a draw), scores, end_reason, turn_count, ply.
+        """
+        if self.state != "GAME_OVER":
+            return None
+        return {
+            "winner": self.winner,
+            "scores": self.get_scores(),
+            "end_reason": self.end_reason,
+            "turn_count": self.turn_count,
+            "ply": self.ply,
+        }
 
-        return actions
+    def _start_mask(self, player):
+        return self.is_p0_start_cell if player == 0 else self.is_p1_start_cell
+
+    def start_cells(self, player):
+        """Coords of the player's start cells, derived from the start masks."""
+        mask = self._start_mask(player)
+        return [self.cell_id_to_coord[cid] for cid in range(self.num_cells) if mask[cid]]
+
+    def at_unit_cap(self, owner):
+        """True if owner may not add more units. UNIT_CAP == 0 disables the cap."""
+        if self.UNIT_CAP == 0:
+            return False
+        return self.get_player_unit_count(owner) >= self.UNIT_CAP
+
+    def material(self):
+        """(p0 bots, p0 tanks, p1 bots, p1 tanks) on the board."""
+        self._ensure_cache()
+        totals = [0, 0, 0, 0]
+        for cid in range(self.num_cells):
+            owner = self.unit_owner[cid]
+            if owner != -1:
+                totals[owner * 2 + self.unit_kind[cid] - 1] += self.unit_count[cid]
+        return tuple(totals)
+
+    def supply_used(self, player):
+        """Units player has on the board, the count UNIT_CAP limits."""
+        return self.get_player_unit_count(player)
 
     def get_player_unit_count(self, owner):
         self._ensure_cache()
         return self.owner_total_units[owner]
 
+    def _check_cell_id(self, cell):
+        if not 0 <= cell < self.num_cells:
+            raise ValueError(f"Not a cell id: {cell}")
+
+    def is_empty(self, cell):
+        """True if no unit stands on cell id."""
+        self._check_cell_id(cell)
+        self._ensure_cache()
+        return self.unit_owner[cell] == -1
+
+    def is_owned_by(self, cell, player):
+        """True if player's units stand on cell id."""
+        self._check_cell_id(cell)
+        self._ensure_cache()
+        return self.unit_owner[cell] == player
+
+    def stacks(self, player):
+        """(cell id, kind, count) for each of player's stacks, largest first, ties by cell id.
+
+        kind uses the unit_kind codes: 1 bot, 2 tank.
+        """
+        self._ensure_cache()
+        cells = self.owner_bot_cells[player] | self.owner_tank_cells[player]
+        out = [(cid, self.unit_kind[cid], self.unit_count[cid]) for cid in cells]
+        out.sort(key=lambda s: (-s[2], s[0]))
+        return out
+
+    def bitboards(self):
+        """Cell sets as int bitmasks, bit i standing for cell id i.
+
+        Keys: p0, p1, bots, tanks, artefacts, empty. Python ints have no width limit,
+        so one int covers the whole board.
+        """
+        self._ensure_cache()
+        boards = dict.fromkeys(('p0', 'p1', 'bots', 'tanks', 'artefacts', 'empty'), 0)
+        for cid in range(self.num_cells):
+            bit = 1 << cid
+            owner = self.unit_owner[cid]
+            if owner == -1:
+                boards['empty'] |= bit
+            else:
+                boards['p0' if owner == 0 else 'p1'] |= bit
+                boards['tanks' if self.unit_kind[cid] == 2 else 'bots'] |= bit
+            if self.is_artefact_cell[cid]:
+                boards['artefacts'] |= bit
+        return boards
+
     def _build_topology(self):
         cell_id_to_coord = []
         coord_to_cell_id = {}
@@ -424,6 +1265,8 @@ class SatellitesGame:
                 cell_id_to_coord.append(coord)
                 coord_to_cell_id[coord] = cid
 
+        # Neighbor order is part of the action encoding (GlobalActionSpace move indices):
+        # left, right, up-left, up-right, down-left, down-right, skipping off-board cells.
         neighbors_by_cell_id = []
         for r, c in cell_id_to_coord:
             directions = [
@@ -471,6 +1314,31 @@ class SatellitesGame:
                     queue.append(nxt)
         return tuple(tuple(row) for row in distance)
 
+    def cell_id(self, r, c):
+        """Flat cell id of (r, c). Raises ValueError off the board."""
+        cid = self.coord_to_cell_id.get((r, c))
+        if cid is None:
+            raise ValueError(f"Not a board cell: {(r, c)}")
+        return cid
+
+    def cell_coords(self, cell):
+        """(r, c) of a flat cell id. Raises ValueError for ids off the board."""
+        self._check_cell_id(cell)
+        return self.cell_id_to_coord[cell]
+
+    def cell_neighbors(self):
+        """Adjacency list by cell id: entry i lists the cell ids next to cell i."""
+        return [[self.coord_to_cell_id[n] for n in nbrs] for nbrs in self.neighbors_by_cell_id]
+
+    def num_edges(self):
+        """Directed neighbor pairs on the board (each adjacency counted both ways)."""
+        return sum(len(nbrs) for nbrs in self.neighbors_by_cell_id)
+
+    def bfs_distances(self, start):
+        """Hop count from cell id start to every cell id (-1 if unreachable)."""
+        self._check_cell_id(start)
+        return list(self.distance_by_cell_id[start])
+
     def get_hex_neighbors(self, r, c):
         cell_id = self.coord_to_cell_id.get((r, c))
         if cell_id is None:
@@ -484,6 +1352,61 @@ class SatellitesGame:
             return -1
         return self.distance_by_cell_id[a_id][b_id]
 
+    def nearest_artefact(self, cid):
+        """(artefact cell id, hex distance) of the remaining artefact closest to cell id, or None.
+
+        Ties go to the artefact with the lowest cell id.
+        """
+        self._check_cell_id(cid)
+        best = None
+        for art in self.artefacts:
+            aid = self.coord_to_cell_id[art]
+            key = (self.distance_by_cell_id[cid][aid], aid)
+            if best is None or key < best:
+                best = key
+        if best is None:
+            return None
+        return best[1], best[0]
+
+    def reachable_artefacts(self, player, within):
+        """Cell ids of remaining artefacts within `within` hops of any of player's units.
+
+        Hops ignore occupancy, so this is a reach estimate rather than a legal path.
+        """
+        self._ensure_cache()
+        units = self.owner_bot_cells[player] | self.owner_tank_cells[player]
+        out = []
+        for art in self.artefacts:
+            aid = self.coord_to_cell_id[art]
+            if any(self.distance_by_cell_id[cid][aid] <= within for cid in units):
+                out.append(aid)
+        return sorted(out)
+
+    def empty_regions(self):
+        """Connected groups of unit-free cells (artefact cells included), as cell id lists.
+
+        Regions are ordered by their lowest cell id; ids within a region ascend.
+        """
+        self._ensure_cache()
+        seen = [False] * self.num_cells
+        regions = []
+        for start in range(self.num_cells):
+            if seen[start] or self.unit_owner[start] != -1:
+                continue
+            seen[start] = True
+            stack = [start]
+            region = []
+            while stack:
+                cur = stack.pop()
+                region.append(cur)
+                for coord in self.neighbors_by_cell_id[cur]:
+                    nxt = self.coord_to_cell_id[coord]
+                    if not seen[nxt] and self.unit_owner[nxt] == -1:
+                        seen[nxt] = True
+                        stack.append(nxt)
+            regions.append(sorted(region))
+        return regions
+
     def check_actions_still_possible(self):
         """Checks if any valid moves remain for the current action type. If not, auto-end turn."""
         if not self.action_type:
@@ -498,7 +1421,7 @@ class SatellitesGame:
         # 1. ADD VALID?
         if "add" in self.action_type:
             # Check Cap
-            if self.get_player_unit_count(self.turn) >= 20:
+            if self.at_unit_cap(self.turn):
                 can_act = False
             else:
                 # Check Placement Locations
@@ -521,23 +1444,23 @@ class SatellitesGame:
                         can_act = len(self.owner_bot_cells[self.turn]) > 0
                     # 2. Empty Start Zones?
                     if not can_act:
-                        starts = [(0,3), (0,4)] if self.turn == 0 else [(8,3), (8,4)]
-                        for pos in starts:
-                            if pos not in self.grid:
+                        start_mask = self._start_mask(self.turn)
+                        for cid in range(self.num_cells):
+                            if start_mask[cid] and self.unit_owner[cid] == -1:
                                 can_act = True
                                 break
         
         # 2. MOVE VALID?
         elif "move" in self.action_type:
             # Check if user has ANY units of this type that can move
-            opp_starts = [(8,3), (8,4)] if self.turn == 0 else [(0,3), (0,4)]
+            opp_start_mask = self._start_mask(1 - self.turn)
             for pos, unit in self.grid.items():
                 if unit['owner'] == self.turn and unit['type'] == req_type:
                     # Check neighbors for THIS unit
                     neighbors = self.get_hex_neighbors(pos[0], pos[1])
                     for nr, nc in neighbors:
                         # NEW RULE: No entry to opponent starting hexes
-                        if (nr, nc) in opp_starts: continue
+                        if opp_start_mask[self.coord_to_cell_id[(nr, nc)]]: continue
 
                         target_cell = self.grid.get((nr,nc))
                         
@@ -566,18 +1489,67 @@ class SatellitesGame:
             if not self.headless:
                 self.info_message = f"Skipped: No valid {self.action_type} actions."
 
+    def _player_has_board_action(self, player, sat_types=None):
+        """True if player has a legal add or move under any of sat_types (default: all types)."""
+        probe = self.clone()
+        probe.turn = player
+        probe.state = "PERFORM_ACTIONS"
+        probe.actions_remaining = 1
+        for sat_type in (self.SAT_TYPE_CODES if sat_types is None else sat_types):
+            probe.action_type = sat_type
+            if probe.legal_actions():
+                return True
+        return False
+
+    def has_any_legal_action(self, max_move_amount=None):
+        """True if the side to move has a legal action now; moves above max_move_amount don't count."""
+        return next(self._iter_legal_actions(max_move_amount), None) is not None
+
+    def is_stalemate(self):
+        """True if neither player can ever change the board again.
+
+        Every legal add or move changes the board, and satellite charges only decide
+        which action type is offered. If neither player has a legal action under any
+        satellite type, the board is frozen for the rest of the game.
+        """
+        if self.state == "GAME_OVER":
+            return False
+        return not self._player_has_board_action(self.turn) and not self._player_has_board_action(1 - self.turn)
+
+    def check_satellites_charged(self):
+        """Ends the game if no satellite holds a charge at the start of a turn.
+
+        Skipping a turn never adds charges to the ring, so neither player could act again.
+        """
+        if self.state != "CHOOSE_SATELLITE":
+            return
+        if any(sat['charges'] > 0 for sat in self.satellites):
+            return
+        self._end_game_on_scores("no_charges", "No Charged Satellites Remain.")
+
+    def _end_game_on_scores(self, reason, message):
+        """End the game early: higher score wins, equal scores draw."""
+        self.state = "GAME_OVER"
+        self.end_reason = reason
+        self.info_message = message
+        if self.scores[0] > self.scores[1]: self.winner = 0
+        elif self.scores[1] > self.scores[0]: self.winner = 1
+        else: self.winner = self._resolve_tie(-1) # Draw
+
     def execute_add(self, r, c):
+        self.last_changed_cells = []
         # 1. SECURITY CHECK: State
         if self.state != "PERFORM_ACTIONS": return False
         if "add" not in self.action_type: return False
+        cid = self.coord_to_cell_id.get((r,c))
+        if cid is None: return False
 
         unit_type = 'tank' if 'tank' in self.action_type else 'bot'
         current = self.grid.get((r,c))
         
         # 2. SECURITY CHECK: Unit Cap
-        current_count = self.get_player_unit_count(self.turn)
-        if current_count >= 20:
-             self.info_message = "Unit Cap Reached (20 Max)!"
+        if self.at_unit_cap(self.turn):
+             self.info_message = f"Unit Cap Reached ({self.UNIT_CAP} Max)!"
              return False
 
         # 3. SECURITY CHECK: Valid Placement Location
@@ -596,8 +1568,7 @@ class SatellitesGame:
                 return False
 
             # 2. Must not be opponent start zone
-            opp_starts = [(8,3), (8,4)] if self.turn == 0 else [(0,3), (0,4)]
-            if (r,c) in opp_starts:
+            if self._start_mask(1 - self.turn)[cid]:
                 self.info_message = "Cannot place in opponent start zone."
                 return False
 
@@ -611,8 +1582,11 @@ class SatellitesGame:
                 current['count'] += 1
             else:
                 self.grid[(r,c)] = {'owner': self.turn, 'type': 'tank', 'count': 1}
-            self._cache_dirty = True
+            self._refresh_cells([(r, c)])
             self.actions_remaining -= 1
+            self.halfmove_clock += 1
+            self.turn_actions_taken += 1
+            self.last_changed_cells = [self.coord_to_cell_id[(r,c)]]
             self.info_message = f"Added tank. Actions: {self.actions_remaining}"
             
             if self.actions_remaining <= 0:
@@ -627,25 +1601,24 @@ class SatellitesGame:
             is_own_stack = (current and current['owner'] == self.turn and current['type'] == unit_type)
             
             # 2. Starting Zones (if empty or own)
-            valid_starts = [(0,3), (0,4)] if self.turn == 0 else [(8,3), (8,4)]
-            is_start_zone = ((r,c) in valid_starts) and (not current or is_own_stack)
+            is_start_zone = self._start_mask(self.turn)[cid] and (not current or is_own_stack)
 
             if not (is_own_stack or is_start_zone):
                 self.info_message = "Bots: Drop on Start Zone or Own Stack"
                 return False
 
             # --- EXECUTION ---
+            self.halfmove_clock += 1
+            self.turn_actions_taken += 1
+            self.last_changed_cells = [self.coord_to_cell_id[(r,c)]]
             if current:
                 current['count'] += 1
-                self._cache_dirty = True
-                self.actions_remaining -= 1
-                self.info_message = f"Added {unit_type}. Actions: {self.actions_remaining}"
             else:
                 self.grid[(r,c)] = {'owner': self.turn, 'type': unit_type, 'count': 1}
-                self._cache_dirty = True
-                self.actions_remaining -= 1
-                self.info_message = f"Added {unit_type}. Actions: {self.actions_remaining}"
-                
+            self._refresh_cells([(r, c)])
+            self.actions_remaining -= 1
+            self.info_message = f"Added {unit_type}. Actions: {self.actions_remaining}"
+
             if self.actions_remaining <= 0:
                 self.end_turn()
             else:
@@ -702,20 +1675,32 @@ class SatellitesGame:
                     else:
                         self.info_message = f"Wrong unit type! Satellite needs {req_type}."
 
-    def check_win(self):
-        # 1. Score >= 9
-        if self.scores[self.turn] >= 9:
-            self.winner = self.turn
-            self.state = "GAME_OVER"
-            return True
+    def compute_winner(self):
+        """Winner check_win would declare right now, or None. Does not mutate the game."""
+        # 1. Score >= WIN_SCORE
+        if self.scores[self.turn] >= self.WIN_SCORE:
+            return self.turn
         # 2. All Artefacts Captured
         if len(self.artefacts) == 0:
-            if self.scores[0] > self.scores[1]: self.winner = 0
-            elif self.scores[1] > self.scores[0]: self.winner = 1
-            else: self.winner = self.turn # Tie-breaker
-            self.state = "GAME_OVER"
-            return True
-        return False
+            if self.scores[0] > self.scores[1]: return 0
+            elif self.scores[1] > self.scores[0]: return 1
+            else: return self._resolve_tie(self.turn) # Tie-breaker
+        return None
+
+    def _resolve_tie(self, default):
+        """Winner of a tied game: default, or a seeded pick under the "random" policy."""
+        if self.TIE_POLICY != "random":
+            return default
+        return (self.zobrist_hash() ^ _zobrist_key("tie_seed", self.tie_seed)) >> 63
+
+    def check_win(self):
+        winner = self.compute_winner()
+        if winner is None:
+            return False
+        self.winner = winner
+        self.state = "GAME_OVER"
+        self.end_reason = "score_limit" if self.scores[self.turn] >= self.WIN_SCORE else "artefacts_exhausted"
+        return True
 
    # In SatellitesGame class
 
@@ -725,6 +1710,7 @@ class SatellitesGame:
         # 1. SECURITY CHECKS
         # Failures must now return a tuple: (False, 0, 0)
         # 0 kills, 0 points
+        self.last_changed_cells = []
         if start not in self.grid: return False, 0, 0
         cell = self.grid[start]
         if cell['owner'] != self.turn: return False, 0, 0
@@ -734,8 +1720,7 @@ class SatellitesGame:
             self.info_message = "Invalid Move: Not adjacent"
             return False, 0, 0
 
-        opp_starts = [(8,3), (8,4)] if self.turn == 0 else [(0,3), (0,4)]
-        if end in opp_starts:
+        if self._start_mask(1 - self.turn)[self.coord_to_cell_id[end]]:
             self.info_message = "Cannot move onto opponent starting hex!"
             return False, 0, 0
 
@@ -745,6 +1730,7 @@ class SatellitesGame:
             return False, 0, 0
 
         # --- EXECUTION ---
+        cache_clean = not self._cache_dirty
         cell['count'] -= amount
         self._cache_dirty = True
         if cell['count'] < 0: return False, 0, 0
@@ -786,6 +1772,8 @@ class SatellitesGame:
 
                 # Successful Kill
                 units_destroyed = target['count'] 
+                self.units_captured[self.turn] += units_destroyed
+                self.units_lost[target['owner']] += units_destroyed
                 del self.grid[end]
                 
                 if move_type == 'tank':
@@ -799,15 +1787,34 @@ class SatellitesGame:
             # Move to empty
             self.grid[end] = {'owner': self.turn, 'type': move_type, 'count': amount}
         
+        # Only start and end changed, so a cache that was clean can be patched in place.
+        if cache_clean:
+            self._cache_dirty = False
+            self._refresh_cells([start, end])
+
         # --- ARTEFACT LOGIC ---
         if did_move_in and end in self.artefacts:
             self.artefacts.remove(end)
             self.is_artefact_cell[self.coord_to_cell_id[end]] = False
-            # Rule: 1 point per bot in the stack
-            score_gain = amount  
+            # Rule: 1 point per bot in the stack (scaled by the cell's artefact value)
+            score_gain = amount * self.artefact_value[self.coord_to_cell_id[end]]
             self.scores[self.turn] += score_gain 
             self.info_message = f"Captured Artefact! +{score_gain} pts"
 
+        # A tank shot leaves the source stack untouched.
+        if did_move_in:
+            self.last_changed_cells = [self.coord_to_cell_id[start], self.coord_to_cell_id[end]]
+        else:
+            self.last_changed_cells = [self.coord_to_cell_id[end]]
+
+        self.turn_actions_taken += 1
+
+        # Captures and scores reset the halfmove clock.
+        if units_destroyed or score_gain:
+            self.halfmove_clock = 0
+        else:
+            self.halfmove_clock += 1
+
         if self.check_win():
              return True, units_destroyed, score_gain
 
@@ -830,12 +1837,45 @@ class SatellitesGame:
             
         return True, units_destroyed, score_gain
 
+    def move_scores_artefact(self, start, end, amount):
+        """True if this legal move would move units onto an artefact and capture it."""
+        if not self._is_legal_move(start, end, amount):
+            return False
+        eid = self.coord_to_cell_id[end]
+        # Tank attacks hold position, so only a move-in can capture.
+        did_move_in = not (self.unit_kind[self.coord_to_cell_id[start]] == 2 and self.unit_owner[eid] == 1 - self.turn)
+        return did_move_in and self.is_artefact_cell[eid]
+
+    def predict_move(self, start, end, amount):
+        """Dry-run a move on a clone and describe its effect without mutating this game.
+
+        Returns {'legal': False} for illegal moves. Otherwise also reports the resulting
+        src/dst stack counts and owners, units destroyed, and score gained.
+        """
+        if not self._is_legal_move(start, end, amount):
+            return {'legal': False}
+        sim = self.clone()
+        _, kills, score_gain = sim.execute_move(start, end, amount)
+        src = sim.grid.get(start)
+        dst = sim.grid.get(end)
+        return {
+            'legal': True,
+            'src_count': src['count'] if src else 0,
+            'dst_count': dst['count'] if dst else 0,
+            'dst_owner': dst['owner'] if dst else -1,
+            'capture': kills > 0,
+            'units_destroyed': kills,
+            'score_gain': score_gain,
+        }
+
     def select_satellite(self, idx):
+        self.last_changed_cells = []
         if self.state != "CHOOSE_SATELLITE": return
         sat = self.satellites[idx]
         if sat['charges'] > 0:
             self.active_satellite_idx = idx
             self.action_type = sat['type']
+            self.halfmove_clock += 1
             self.picked_up_charges = sat['charges']
             
             # Remove charges immediately
@@ -849,6 +1889,8 @@ class SatellitesGame:
 
     def set_distribution_direction(self, clockwise):
         self.distribution_direction = 1 if clockwise else -1
+        self.halfmove_clock += 1
+        self.last_changed_cells = []
         
         # Distribute Immediately
         self.perform_distribution()
@@ -874,7 +1916,7 @@ class SatellitesGame:
             can_add = False
             
             # 1. Check Cap
-            if self.get_player_unit_count(self.turn) >= 20: 
+            if self.at_unit_cap(self.turn): 
                 can_add = False # At cap, no adds allowed
             else:
                 if req_type == 'tank':
@@ -894,9 +1936,9 @@ class SatellitesGame:
                     
                     # 3. Empty Start Zones?
                     if not can_add:
-                        starts = [(0,3), (0,4)] if self.turn == 0 else [(8,3), (8,4)]
-                        for pos in starts:
-                            if pos not in self.grid: # Empty start zone
+                        start_mask = self._start_mask(self.turn)
+                        for cid in range(self.num_cells):
+                            if start_mask[cid] and self.unit_owner[cid] == -1: # Empty start zone
                                 can_add = True
                                 break
             
@@ -914,22 +1956,52 @@ class SatellitesGame:
 
     def perform_distribution(self):
         # Use stored direction
-        to_distribute = self.picked_up_charges 
-        idx = self.active_satellite_idx
-        
-        while to_distribute > 0:
-            idx = (idx + self.distribution_direction) % 6
-            self.satellites[idx]['charges'] += 1
-            to_distribute -= 1
+        charges = rotate_charges(
+            [sat['charges'] for sat in self.satellites],
+            self.active_satellite_idx,
+            self.picked_up_charges,
+            self.distribution_direction,
+        )
+        for sat, charge in zip(self.satellites, charges):
+            sat['charges'] = charge
 
     def end_turn(self):
+        """Pass play to the other player, or end the game if a limit is hit.
+
+        The turn limit is checked whenever a turn ends: the game is over once
+        turn_count (full rounds, starting at 1) has reached MAX_TURNS. Player 1
+        therefore never plays round MAX_TURNS, and MAX_TURNS <= 1 (including 0)
+        ends the game as soon as player 0's first turn ends.
+        """
+        self.ply += 1
+
+        # A forced pass: nothing was added or moved, and no satellite on offer this
+        # turn (still charged, or the one just used) had a legal action.
+        offered = {sat['type'] for sat in self.satellites if sat['charges'] > 0}
+        if self.action_type:
+            offered.add(self.action_type)
+        if self.turn_actions_taken == 0 and not self._player_has_board_action(self.turn, offered):
+            self.consecutive_passes += 1
+        else:
+            self.consecutive_passes = 0
+        self.turn_actions_taken = 0
+
         # FIX: Check Turn Limit
         if self.turn_count >= self.MAX_TURNS:
+            self._end_game_on_scores("turn_limit", "Max Turn Limit Reached.")
+            return
+
+        # Stale game: no capture or score for too long.
+        if self.MAX_HALFMOVE_CLOCK is not None and self.halfmove_clock >= self.MAX_HALFMOVE_CLOCK:
             self.state = "GAME_OVER"
-            self.info_message = "Max Turn Limit Reached."
-            if self.scores[0] > self.scores[1]: self.winner = 0
-            elif self.scores[1] > self.scores[0]: self.winner = 1
-            else: self.winner = -1 # Draw
+            self.end_reason = "halfmove_limit"
+            self.info_message = "Halfmove Clock Limit Reached."
+            self.winner = -1 # Draw
+            return
+
+        # Both players were just forced to pass in turn.
+        if self.consecutive_passes >= 2:
+            self._end_game_on_scores("both_passed", "Both Players Passed.")
             return
 
         self.turn = 1 - self.turn
@@ -945,6 +2017,41 @@ class SatellitesGame:
         if not self.headless and "Skipped" not in self.info_message:
             self.info_message = f"{p_name}'s Turn. Choose Satellite."
 
+        self.check_satellites_charged()
+
+
+_DIFF_FIELDS = (
+    "scores", "turn", "state", "active_satellite_idx", "actions_remaining",
+    "picked_up_charges", "action_type", "winner", "end_reason", "turn_count",
+    "halfmove_clock", "ply", "units_captured", "units_lost", "satellites", "artefacts",
+    "artefact_value", "turn_actions_taken", "consecutive_passes", "is_p0_start_cell",
+    "is_p1_start_cell", "UNIT_CAP", "WIN_SCORE", "MAX_TURNS", "MAX_HALFMOVE_CLOCK",
+    "TIE_POLICY", "tie_seed",
+)
+
+
+def game_diff(a, b):
+    """Where two games disagree: (cells, fields).
+
+    cells lists (cell id, owner, kind, count) with b's values for every cell whose
+    occupant differs (owner -1 and kind 0 for empty). fields maps each differing
+    rules attribute to its (a value, b value) pair.
+    """
+    a._ensure_cache()
+    b._ensure_cache()
+    cells = [
+        (cid, b.unit_owner[cid], b.unit_kind[cid], b.unit_count[cid])
+        for cid in range(a.num_cells)
+        if (a.unit_owner[cid], a.unit_kind[cid], a.unit_count[cid])
+        != (b.unit_owner[cid], b.unit_kind[cid], b.unit_count[cid])
+    ]
+    fields = {}
+    for name in _DIFF_FIELDS:
+        va, vb = getattr(a, name), getattr(b, name)
+        if va != vb:
+            fields[name] = (va, vb)
+    return cells, fields
+
 
 # ==========================================
 # PART 2: PYGAME UI
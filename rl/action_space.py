@@ -1,6 +1,7 @@
 from __future__ import annotations
 
-from typing import Any, Dict, List, Tuple
+from types import MappingProxyType
+from typing import Any, Dict, Iterable, List, Mapping, Sequence, Tuple
 
 import numpy as np
 
@@ -8,6 +9,39 @@ from engine import SatellitesGame
 
 Action = Any
 
+# Built action tables keyed by max_move_amount. The board topology is fixed, so every
+# action space with the same amount limit shares one table, stored as a tuple and a
+# read-only mapping so no caller can change it under the others.
+_TABLE_CACHE: Dict[int, Tuple[Tuple[Action, ...], Mapping[Action, int]]] = {}
+
+Coord = Tuple[int, int]
+_EDGE_CACHE: Tuple[Tuple[Coord, Coord], ...] = ()
+_EDGE_ORDINALS: Dict[Tuple[Coord, Coord], int] = {}
+
+
+def edge_list(game: SatellitesGame) -> Tuple[Tuple[Coord, Coord], ...]:
+    """Directed (src, dst) neighbor pairs, indexed by edge ordinal. Built once and shared.
+
+    Ordinals follow source cell id, then neighbor order, matching the move block layout.
+    game only supplies the board topology, which every SatellitesGame shares, so the
+    first call builds the tuple and later calls return it whatever game they pass.
+    """
+    global _EDGE_CACHE
+    if not _EDGE_CACHE:
+        edges = []
+        for src_id, src in enumerate(game.cell_id_to_coord):
+            for dst in game.neighbors_by_cell_id[src_id]:
+                _EDGE_ORDINALS[(src, dst)] = len(edges)
+                edges.append((src, dst))
+        _EDGE_CACHE = tuple(edges)
+    return _EDGE_CACHE
+
+
+def edge_ordinal(game: SatellitesGame, src: Coord, dst: Coord) -> int | None:
+    """Ordinal of the directed edge src -> dst in edge_list(game), or None if not adjacent."""
+    edge_list(game)
+    return _EDGE_ORDINALS.get((tuple(src), tuple(dst)))
+
 
 class GlobalActionSpace:
     """Fixed action indexing for policy networks."""
@@ -15,35 +49,44 @@ class GlobalActionSpace:
     def __init__(self, game_template: SatellitesGame | None = None, max_move_amount: int = 20):
         self.game_template = game_template or SatellitesGame(headless=True)
         self.max_move_amount = max_move_amount
-        self.index_to_action: List[Action] = []
-        self.action_to_index: Dict[Action, int] = {}
+        self.edges = edge_list(self.game_template)
+        self.index_to_action: Sequence[Action] = ()
+        self.action_to_index: Mapping[Action, int] = {}
         self._build()
 
     @property
     def size(self) -> int:
         return len(self.index_to_action)
 
-    def _add(self, action: Action) -> None:
-        idx = len(self.index_to_action)
-        self.index_to_action.append(action)
-        self.action_to_index[action] = idx
+    @property
+    def move_base(self) -> int:
+        """Index of the first move action: satellites, directions, then one add per cell."""
+        return 6 + 2 + self.game_template.num_cells
+
+    def edge_endpoints(self, ordinal: int) -> Tuple[Coord, Coord]:
+        """(src, dst) of a move edge ordinal."""
+        return self.edges[ordinal]
 
     def _build(self) -> None:
-        # Satellite choice.
-        for i in range(6):
-            self._add(("select_satellite", i))
-        # Direction choice.
-        self._add(("set_direction", False))
-        self._add(("set_direction", True))
-        # Adds for every board cell.
-        for coord in self.game_template.cell_id_to_coord:
-            self._add(("add", coord[0], coord[1]))
-        # Move actions for directed adjacent pairs with amount 1..max_move_amount.
-        for src in self.game_template.cell_id_to_coord:
-            src_id = self.game_template.coord_to_cell_id[src]
-            for dst in self.game_template.neighbors_by_cell_id[src_id]:
+        cached = _TABLE_CACHE.get(self.max_move_amount)
+        if cached is None:
+            actions: List[Action] = []
+            # Satellite choice.
+            for i in range(6):
+                actions.append(("select_satellite", i))
+            # Direction choice.
+            actions.append(("set_direction", False))
+            actions.append(("set_direction", True))
+            # Adds for every board cell.
+            for coord in self.game_template.cell_id_to_coord:
+                actions.append(("add", coord[0], coord[1]))
+            # Move actions for directed adjacent pairs with amount 1..max_move_amount.
+            for src, dst in self.edges:
                 for amount in range(1, self.max_move_amount + 1):
-                    self._add(("move", src, dst, amount))
+                    actions.append(("move", src, dst, amount))
+            cached = (tuple(actions), MappingProxyType({action: idx for idx, action in enumerate(actions)}))
+            _TABLE_CACHE[self.max_move_amount] = cached
+        self.index_to_action, self.action_to_index = cached
 
     def to_index(self, action: Action) -> int:
         return self.action_to_index[action]
@@ -51,6 +94,89 @@ class GlobalActionSpace:
     def from_index(self, index: int) -> Action:
         return self.index_to_action[index]
 
+    def describe(self, index: int) -> str:
+        """Human-readable description of an action index, for logs and transcripts."""
+        if not 0 <= index < self.size:
+            return f"invalid action index {index} (size {self.size})"
+        action = self.index_to_action[index]
+        kind = action[0]
+        if kind == "select_satellite":
+            return f"choose satellite slot {action[1]}"
+        if kind == "set_direction":
+            return f"distribute {'clockwise' if action[1] else 'counter-clockwise'}"
+        if kind == "add":
+            return f"add at {(action[1], action[2])}"
+        return f"move {action[3]} from {action[1]} to {action[2]}"
+
+    def add_index(self, r: int, c: int) -> int:
+        """Index of ('add', r, c)."""
+        idx = self.action_to_index.get(("add", r, c))
+        if idx is None:
+            raise ValueError(f"Not a board cell: {(r, c)}")
+        return idx
+
+    def move_index(self, start: Tuple[int, int], end: Tuple[int, int], amount: int) -> int:
+        """Index of ('move', start, end, amount)."""
+        if not 1 <= amount <= self.max_move_amount:
+            raise ValueError(f"Move amount {amount} outside 1..{self.max_move_amount}")
+        ordinal = edge_ordinal(self.game_template, start, end)
+        if ordinal is None:
+            raise ValueError(f"No move edge from {start} to {end}")
+        return self.move_base + ordinal * self.max_move_amount + (amount - 1)
+
+    def apply_add(self, game: SatellitesGame, r: int, c: int) -> bool:
+        """Apply ('add', r, c) if it is legal in game's current phase. Returns success."""
+        return game.try_add(r, c)
+
+    def apply_move(self, game: SatellitesGame, start: Tuple[int, int], end: Tuple[int, int], amount: int) -> bool:
+        """Apply ('move', start, end, amount) if legal and within max_move_amount. Returns success."""
+        if not 1 <= amount <= self.max_move_amount:
+            return False
+        return game.try_move(tuple(start), tuple(end), amount)
+
+    def mirror_index(self, index: int) -> int:
+        """Index of the action that index maps to under SatellitesGame.mirror()."""
+        return self.action_to_index[self.game_template.mirror_action(self.index_to_action[index])]
+
+    def step(self, game: SatellitesGame, index: int, illegal_penalty: float = -1.0) -> Tuple[float, bool]:
+        """Apply action index to game and return (reward, done).
+
+        reward is the score the acting player gained from the action. An illegal index
+        leaves game untouched and returns illegal_penalty.
+        """
+        if index not in self.legal_action_indices(game):
+            return illegal_penalty, game.state == "GAME_OVER"
+        actor = game.turn
+        before = game.scores[actor]
+        game.apply_action(self.index_to_action[index])
+        return float(game.scores[actor] - before), game.state == "GAME_OVER"
+
+    # Phase in which each action kind can be played.
+    ACTION_PHASES: Dict[str, str] = {
+        "select_satellite": "CHOOSE_SATELLITE",
+        "set_direction": "CHOOSE_DIRECTION",
+        "add": "PERFORM_ACTIONS",
+        "move": "PERFORM_ACTIONS",
+    }
+
+    def classify_index(self, game: SatellitesGame, index: int) -> str:
+        """Why index would or would not apply in game: "legal", "wrong_phase" or "illegal".
+
+        "illegal" means the action belongs to the current phase but is rejected, e.g. an
+        add during a move turn or a move larger than the stack. Raises ValueError for an
+        index outside the action space, which usually means the sizing has drifted.
+        """
+        if not 0 <= index < self.size:
+            raise ValueError(
+                f"Action index {index} outside 0..{self.size - 1} "
+                f"(max_move_amount={self.max_move_amount}, {self.game_template.num_cells} cells)"
+            )
+        if self.ACTION_PHASES[self.index_to_action[index][0]] != game.state:
+            return "wrong_phase"
+        if index in self.legal_action_indices(game):
+            return "legal"
+        return "illegal"
+
     def legal_action_indices(self, game: SatellitesGame) -> List[int]:
         out: List[int] = []
         for action in game.legal_actions():
@@ -59,28 +185,93 @@ class GlobalActionSpace:
                 out.append(idx)
         return out
 
+    def legal_actions_decoded(self, game: SatellitesGame) -> List[Action]:
+        """Legal actions as engine tuples, in index order; moves beyond max_move_amount are dropped."""
+        return [self.index_to_action[idx] for idx in sorted(self.legal_action_indices(game))]
+
+    def legal_actions_filtered(self, game: SatellitesGame, candidates: Iterable[int]) -> List[int]:
+        """The candidates that are legal in game, in candidate order, without duplicates."""
+        legal = set(self.legal_action_indices(game))
+        out: List[int] = []
+        for idx in candidates:
+            idx = int(idx)
+            if idx in legal:
+                out.append(idx)
+                legal.discard(idx)
+        return out
+
     def legal_action_mask(self, game: SatellitesGame) -> np.ndarray:
+        """Dense (size,) boolean mask, True exactly at legal_action_indices(game)."""
         mask = np.zeros(self.size, dtype=np.bool_)
-        for idx in self.legal_action_indices(game):
-            mask[idx] = True
+        mask[self.legal_action_indices(game)] = True
         return mask
 
+    def apply_logit_mask(self, game: SatellitesGame, logits: np.ndarray) -> np.ndarray:
+        """Set the logits of illegal actions to -inf in place and return logits."""
+        if logits.shape != (self.size,):
+            raise ValueError(f"Expected logits of shape ({self.size},), got {logits.shape}")
+        if not np.issubdtype(logits.dtype, np.floating):
+            raise ValueError(f"Expected a float array, got {logits.dtype}")
+        logits[~self.legal_action_mask(game)] = -np.inf
+        return logits
+
+    def legal_masks_into(self, games: Sequence[SatellitesGame], out: np.ndarray) -> np.ndarray:
+        """Fill a preallocated (len(games), size) boolean array with legal masks and return it."""
+        if out.dtype != np.bool_:
+            raise ValueError(f"Expected a bool array, got {out.dtype}")
+        if out.shape != (len(games), self.size):
+            raise ValueError(f"Expected out of shape ({len(games)}, {self.size}), got {out.shape}")
+        out.fill(False)
+        for row, game in enumerate(games):
+            out[row, self.legal_action_indices(game)] = True
+        return out
+
+    def argmax_legal(self, game: SatellitesGame, scores: np.ndarray) -> int | None:
+        """Return the legal action index with the highest score, or None if no action is legal.
+
+        Ties are broken towards the lowest action index so greedy play is deterministic.
+        """
+        scores = np.asarray(scores, dtype=np.float32)
+        if scores.shape != (self.size,):
+            raise ValueError(f"Expected scores of shape ({self.size},), got {scores.shape}")
+        best_idx: int | None = None
+        best_score = -np.inf
+        for idx in sorted(self.legal_action_indices(game)):
+            score = float(scores[idx])
+            if best_idx is None or score > best_score:
+                best_idx = idx
+                best_score = score
+        return best_idx
+
     def visit_policy(self, visit_counts: Dict[int, int], temperature: float = 1.0) -> np.ndarray:
-        pi = np.zeros(self.size, dtype=np.float32)
-        if not visit_counts:
-            return pi
-        t = max(1e-6, float(temperature))
-        keys = list(visit_counts.keys())
-        values = np.array([float(visit_counts[k]) for k in keys], dtype=np.float32)
-        if t < 1e-3:
-            best = keys[int(np.argmax(values))]
-            pi[best] = 1.0
-            return pi
-        values = np.power(values, 1.0 / t)
-        total = float(values.sum())
-        if total <= 0.0:
-            return pi
-        for key, v in zip(keys, values):
-            pi[key] = float(v / total)
-        return pi
+        return normalize_visit_counts(list(visit_counts.items()), self.size, temperature)
+
 
+def normalize_visit_counts(
+    counts: Iterable[Tuple[int, int]],
+    action_space_size: int,
+    temperature: float = 1.0,
+) -> np.ndarray:
+    """Scatter (action_index, visits) pairs into a dense policy target.
+
+    Visits are raised to 1/temperature before normalizing; near-zero temperatures
+    collapse onto the most visited action (first one wins ties).
+    """
+    pi = np.zeros(action_space_size, dtype=np.float32)
+    counts = list(counts)
+    if not counts:
+        return pi
+    t = max(1e-6, float(temperature))
+    keys = [int(k) for k, _ in counts]
+    values = np.array([float(v) for _, v in counts], dtype=np.float32)
+    if t < 1e-3:
+        best = keys[int(np.argmax(values))]
+        pi[best] = 1.0
+        return pi
+    values = np.power(values, 1.0 / t)
+    total = float(values.sum())
+    if total <= 0.0:
+        return pi
+    for key, v in zip(keys, values):
+        pi[key] += float(v / total)
+    return pi
@@ -1,11 +1,38 @@
 from __future__ import annotations
 
-from typing import Dict
+from typing import Dict, List, Sequence, Tuple
 
 import numpy as np
 
 from engine import SatellitesGame
 
+# p0_bot, p0_tank, p1_bot, p1_tank, artefact, p0_start, p1_start, adjacent_to_artefact
+CELL_FEATURE_SIZE = 8
+# Global segments after the per-cell block, in encoding order.
+GLOBAL_SEGMENTS: Tuple[Tuple[str, int], ...] = (
+    ("side_to_move", 2),
+    ("scores", 2),
+    ("phase", 4),
+    ("active_satellite", 7),
+    ("counters", 3),
+    ("satellites", 6 * 5),
+)
+
+
+def feature_dim(num_cells: int) -> int:
+    """Length of FeatureEncoder.encode output for a board of num_cells cells."""
+    return num_cells * CELL_FEATURE_SIZE + sum(length for _, length in GLOBAL_SEGMENTS)
+
+
+def feature_layout(num_cells: int) -> List[Tuple[str, int, int]]:
+    """(name, offset, length) of each feature segment, starting with the per-cell block."""
+    layout = [("cells", 0, num_cells * CELL_FEATURE_SIZE)]
+    offset = layout[0][2]
+    for name, length in GLOBAL_SEGMENTS:
+        layout.append((name, offset, length))
+        offset += length
+    return layout
+
 
 class FeatureEncoder:
     """Flat numeric encoder for policy/value training."""
@@ -18,44 +45,156 @@ class FeatureEncoder:
     }
     SAT_TYPES = ("move_tank", "move_bot", "add_tank", "add_bot")
 
-    def __init__(self, game_template: SatellitesGame | None = None):
+    def __init__(
+        self,
+        game_template: SatellitesGame | None = None,
+        *,
+        unit_scale: float | None = None,
+        score_scale: float | None = None,
+        counter_scale: float = 3.0,
+    ):
         self.game_template = game_template or SatellitesGame(headless=True)
+        # Divisors that keep features in [0, 1]. None follows each game's own rules:
+        # UNIT_CAP (20 when uncapped) for stack counts and WIN_SCORE for scores.
+        self.unit_scale = unit_scale
+        self.score_scale = score_scale
+        self.counter_scale = counter_scale
         self.num_cells = self.game_template.num_cells
-        # p0_bot, p0_tank, p1_bot, p1_tank, artefact, p0_start, p1_start
-        self.cell_feature_size = 7
-        # side_to_move(2), scores(2), state(4), active_sat(7), counters(3), satellites(6*5)
-        self.global_feature_size = 2 + 2 + 4 + 7 + 3 + 30
-        self.feature_dim = self.num_cells * self.cell_feature_size + self.global_feature_size
+        self.cell_feature_size = CELL_FEATURE_SIZE
+        self.neighbor_ids = [
+            [self.game_template.coord_to_cell_id[n] for n in self.game_template.neighbors_by_cell_id[cid]]
+            for cid in range(self.num_cells)
+        ]
+        self.global_feature_size = sum(length for _, length in GLOBAL_SEGMENTS)
+        self.feature_dim = feature_dim(self.num_cells)
+
+    def _unit_scale(self, game: SatellitesGame) -> float:
+        if self.unit_scale is not None:
+            return float(self.unit_scale)
+        return float(game.UNIT_CAP or 20)
+
+    def _score_scale(self, game: SatellitesGame) -> float:
+        if self.score_scale is not None:
+            return float(self.score_scale)
+        return float(game.WIN_SCORE)
 
     def encode(self, game: SatellitesGame) -> np.ndarray:
-        game._ensure_cache()
         feat = np.zeros(self.feature_dim, dtype=np.float32)
-        p = 0
+        self._encode_into(game, feat)
+        return feat
 
+    def encode_batch(self, games: Sequence[SatellitesGame], out: np.ndarray | None = None) -> np.ndarray:
+        """Encode games into one C-contiguous float32 array of shape (len(games), feature_dim).
+
+        Row i starts at flat offset i * feature_dim. Pass out to reuse a preallocated buffer.
+        """
+        shape = (len(games), self.feature_dim)
+        if out is None:
+            out = np.zeros(shape, dtype=np.float32)
+        else:
+            if out.dtype != np.float32 or out.shape != shape or not out.flags.c_contiguous:
+                raise ValueError(f"Expected a C-contiguous float32 array of shape {shape}")
+            out.fill(0.0)
+        for row, game in enumerate(games):
+            if game.num_cells != self.num_cells:
+                raise ValueError(f"Game {row} has {game.num_cells} cells, expected {self.num_cells}")
+            self._encode_into(game, out[row])
+        return out
+
+    def encode_delta(self, prev: SatellitesGame, game: SatellitesGame) -> List[Tuple[int, float]]:
+        """(feature index, new value) for every feature that differs between prev and game.
+
+        game must be prev after one action: only the cells in game.last_changed_cells,
+        their neighbors (artefact adjacency) and the global segments are re-encoded.
+        Writing the result into encode(prev) reproduces encode(game) exactly.
+        """
+        prev._ensure_cache()
+        game._ensure_cache()
+        cells = set(game.last_changed_cells)
+        for cid in game.last_changed_cells:
+            cells.update(self.neighbor_ids[cid])
+        size = self.cell_feature_size
+        prev_scale = self._unit_scale(prev)
+        game_scale = self._unit_scale(game)
+        delta: List[Tuple[int, float]] = []
+        for cid in sorted(cells):
+            old = np.zeros(size, dtype=np.float32)
+            new = np.zeros(size, dtype=np.float32)
+            self._encode_cell(prev, cid, old, prev_scale)
+            self._encode_cell(game, cid, new, game_scale)
+            delta += [(cid * size + int(i), float(new[i])) for i in np.flatnonzero(old != new)]
+        globals_start = self.num_cells * size
+        old = np.zeros(self.global_feature_size, dtype=np.float32)
+        new = np.zeros(self.global_feature_size, dtype=np.float32)
+        self._encode_globals(prev, old)
+        self._encode_globals(game, new)
+        delta += [(globals_start + int(i), float(new[i])) for i in np.flatnonzero(old != new)]
+        return delta
+
+    @property
+    def plane_shape(self) -> Tuple[int, int, int]:
+        """(channels, rows, cols) of encode_planes output."""
+        rows = len(self.game_template.row_widths)
+        cols = max(self.game_template.row_widths)
+        return (self.cell_feature_size + self.global_feature_size, rows, cols)
+
+    def encode_planes(self, game: SatellitesGame) -> np.ndarray:
+        """Encode game as float32 planes of shape plane_shape, for conv nets.
+
+        Channels 0..cell_feature_size-1 hold the per-cell features in encode() order;
+        cell (r, c) sits at [:, r, c], so rows are left-aligned and padded with zeros
+        on the right up to the widest row. Each following channel is one global
+        feature from encode(), in order, broadcast over the board cells (padding stays 0).
+        """
+        feat = self.encode(game)
+        planes = np.zeros(self.plane_shape, dtype=np.float32)
+        cells = feat[: self.num_cells * self.cell_feature_size].reshape(self.num_cells, self.cell_feature_size)
+        globals_ = feat[self.num_cells * self.cell_feature_size:]
+        for cid, (r, c) in enumerate(self.game_template.cell_id_to_coord):
+            planes[: self.cell_feature_size, r, c] = cells[cid]
+            planes[self.cell_feature_size:, r, c] = globals_
+        return planes
+
+    def _encode_into(self, game: SatellitesGame, feat: np.ndarray) -> None:
+        """Write game's features into a zeroed feature_dim row."""
+        game._ensure_cache()
+        unit_scale = self._unit_scale(game)
+        size = self.cell_feature_size
         for cid in range(self.num_cells):
-            owner = game.unit_owner[cid]
-            kind = game.unit_kind[cid]
-            cnt = game.unit_count[cid] / 20.0
-            if owner == 0 and kind == 1:
-                feat[p + 0] = cnt
-            elif owner == 0 and kind == 2:
-                feat[p + 1] = cnt
-            elif owner == 1 and kind == 1:
-                feat[p + 2] = cnt
-            elif owner == 1 and kind == 2:
-                feat[p + 3] = cnt
-            feat[p + 4] = 1.0 if game.is_artefact_cell[cid] else 0.0
-            feat[p + 5] = 1.0 if game.is_p0_start_cell[cid] else 0.0
-            feat[p + 6] = 1.0 if game.is_p1_start_cell[cid] else 0.0
-            p += self.cell_feature_size
+            self._encode_cell(game, cid, feat[cid * size:(cid + 1) * size], unit_scale)
+        self._encode_globals(game, feat[self.num_cells * size:])
+
+    def _encode_cell(self, game: SatellitesGame, cid: int, feat: np.ndarray, unit_scale: float) -> None:
+        """Write one cell's features into a zeroed cell_feature_size slice. Needs a fresh cache."""
+        owner = game.unit_owner[cid]
+        kind = game.unit_kind[cid]
+        cnt = game.unit_count[cid] / unit_scale
+        if owner == 0 and kind == 1:
+            feat[0] = cnt
+        elif owner == 0 and kind == 2:
+            feat[1] = cnt
+        elif owner == 1 and kind == 1:
+            feat[2] = cnt
+        elif owner == 1 and kind == 2:
+            feat[3] = cnt
+        feat[4] = 1.0 if game.is_artefact_cell[cid] else 0.0
+        feat[5] = 1.0 if game.is_p0_start_cell[cid] else 0.0
+        feat[6] = 1.0 if game.is_p1_start_cell[cid] else 0.0
+        # One hop from a remaining artefact (staging cells for a capture).
+        feat[7] = 1.0 if any(game.is_artefact_cell[n] for n in self.neighbor_ids[cid]) else 0.0
+
+    def _encode_globals(self, game: SatellitesGame, feat: np.ndarray) -> None:
+        """Write the global segments into a zeroed slice that starts after the cell block."""
+        score_scale = self._score_scale(game)
+        p = 0
 
         # Side to move one-hot.
         feat[p + int(game.turn)] = 1.0
         p += 2
 
         # Scores.
-        feat[p + 0] = float(game.scores[0]) / 9.0
-        feat[p + 1] = float(game.scores[1]) / 9.0
+        feat[p + 0] = float(game.scores[0]) / score_scale
+        feat[p + 1] = float(game.scores[1]) / score_scale
         p += 2
 
         # Phase.
@@ -69,8 +208,8 @@ class FeatureEncoder:
         p += 7
 
         # Counters.
-        feat[p + 0] = float(game.actions_remaining) / 3.0
-        feat[p + 1] = float(game.picked_up_charges) / 3.0
+        feat[p + 0] = float(game.actions_remaining) / self.counter_scale
+        feat[p + 1] = float(game.picked_up_charges) / self.counter_scale
         feat[p + 2] = float(game.turn_count) / float(max(1, game.MAX_TURNS))
         p += 3
 
@@ -79,8 +218,75 @@ class FeatureEncoder:
             t = sat["type"]
             for i, sat_type in enumerate(self.SAT_TYPES):
                 feat[p + i] = 1.0 if t == sat_type else 0.0
-            feat[p + 4] = float(sat["charges"]) / 3.0
+            feat[p + 4] = float(sat["charges"]) / self.counter_scale
             p += 5
 
-        return feat
+    def decode(self, feat: np.ndarray) -> SatellitesGame:
+        """Rebuild a game from an encoded feature vector, as far as the encoding allows.
+
+        Stack counts, scores, counters and charges are denormalized and rounded. Not
+        recoverable: winner, distribution direction, halfmove clock, UI selection state,
+        and the rule settings MAX_TURNS, UNIT_CAP and WIN_SCORE (taken from the
+        template). Start cells come from the board layout.
+        """
+        feat = np.asarray(feat, dtype=np.float32)
+        if feat.shape != (self.feature_dim,):
+            raise ValueError(f"Expected features of shape ({self.feature_dim},), got {feat.shape}")
+        game = SatellitesGame(headless=True)
+        game.MAX_TURNS = self.game_template.MAX_TURNS
+        game.UNIT_CAP = self.game_template.UNIT_CAP
+        game.WIN_SCORE = self.game_template.WIN_SCORE
+        unit_scale = self._unit_scale(game)
+        score_scale = self._score_scale(game)
+        p = 0
+
+        grid = {}
+        artefacts = []
+        for cid in range(self.num_cells):
+            coord = game.cell_id_to_coord[cid]
+            for offset, (owner, utype) in enumerate(((0, "bot"), (0, "tank"), (1, "bot"), (1, "tank"))):
+                count = int(round(float(feat[p + offset]) * unit_scale))
+                if count > 0:
+                    grid[coord] = {"owner": owner, "type": utype, "count": count}
+            if feat[p + 4] > 0.5:
+                artefacts.append(coord)
+            p += self.cell_feature_size
+        game.grid = grid
+        game.artefacts = artefacts
+        game.is_artefact_cell = [False] * game.num_cells
+        for coord in artefacts:
+            game.is_artefact_cell[game.coord_to_cell_id[coord]] = True
+
+        game.turn = int(np.argmax(feat[p:p + 2]))
+        p += 2
+
+        game.scores = [int(round(float(feat[p]) * score_scale)), int(round(float(feat[p + 1]) * score_scale))]
+        p += 2
+
+        phase_idx = int(np.argmax(feat[p:p + 4]))
+        game.state = next(k for k, v in self.STATE_INDEX.items() if v == phase_idx)
+        p += 4
 
+        aidx = int(np.argmax(feat[p:p + 7]))
+        game.active_satellite_idx = None if aidx == 6 else aidx
+        p += 7
+
+        game.actions_remaining = int(round(float(feat[p]) * self.counter_scale))
+        game.picked_up_charges = int(round(float(feat[p + 1]) * self.counter_scale))
+        game.turn_count = max(1, int(round(float(feat[p + 2]) * float(max(1, game.MAX_TURNS)))))
+        p += 3
+
+        satellites = []
+        for _ in range(6):
+            sat_type = self.SAT_TYPES[int(np.argmax(feat[p:p + 4]))]
+            name = " ".join(part.capitalize() for part in sat_type.split("_"))
+            charges = int(round(float(feat[p + 4]) * self.counter_scale))
+            satellites.append({"type": sat_type, "charges": charges, "name": name})
+            p += 5
+        game.satellites = satellites
+        if game.active_satellite_idx is not None:
+            game.action_type = satellites[game.active_satellite_idx]["type"]
+        else:
+            game.action_type = None
+        game.winner = None
+        return game
@@ -0,0 +1,66 @@
+from __future__ import annotations
+
+from typing import List, Optional, Sequence, Tuple
+
+import numpy as np
+
+from engine import SatellitesGame
+from rl.action_space import GlobalActionSpace
+
+
+class VecEnv:
+    """A batch of independent games stepped together with global action indices.
+
+    With auto_reset, a game that ends during a step is replaced by a fresh game
+    before the step returns; the step's done flag and winner still describe the
+    finished game.
+    """
+
+    def __init__(self, num_envs: int, *, max_move_amount: int = 20, auto_reset: bool = False):
+        if num_envs < 1:
+            raise ValueError("num_envs must be at least 1")
+        self.num_envs = num_envs
+        self.auto_reset = auto_reset
+        self.games: List[SatellitesGame] = [SatellitesGame(headless=True) for _ in range(num_envs)]
+        self.action_space = GlobalActionSpace(self.games[0], max_move_amount=max_move_amount)
+
+    def reset(self) -> None:
+        self.games = [SatellitesGame(headless=True) for _ in range(self.num_envs)]
+
+    def legal_action_masks(self) -> np.ndarray:
+        return np.stack([self.action_space.legal_action_mask(g) for g in self.games])
+
+    def step_full(self, actions: Sequence[int]) -> Tuple[np.ndarray, np.ndarray, List[Optional[int]]]:
+        """Apply one action index per env.
+
+        Returns (rewards, dones, winners). Rewards are from the perspective of the player
+        who acted: +1 for a win, -1 for a loss, 0 otherwise. Games that were already over
+        are left alone and report reward 0 and done True. Winners mirror game.winner
+        (-1 for a draw, None while the game is running). Raises ValueError, before
+        stepping any env, if an index is not legal in its running game.
+        """
+        if len(actions) != self.num_envs:
+            raise ValueError(f"Expected {self.num_envs} actions, got {len(actions)}")
+        # Check the whole batch first so an illegal index leaves every env untouched.
+        for i, (game, action_idx) in enumerate(zip(self.games, actions)):
+            if game.state != "GAME_OVER" and int(action_idx) not in self.action_space.legal_action_indices(game):
+                raise ValueError(f"Illegal action {action_idx} in env {i} ({game.state})")
+        rewards = np.zeros(self.num_envs, dtype=np.float32)
+        dones = np.zeros(self.num_envs, dtype=np.bool_)
+        winners: List[Optional[int]] = []
+        for i, (game, action_idx) in enumerate(zip(self.games, actions)):
+            if game.state != "GAME_OVER":
+                actor = game.turn
+                game.apply_action(self.action_space.from_index(int(action_idx)))
+                if game.state == "GAME_OVER" and game.winner is not None and game.winner != -1:
+                    rewards[i] = 1.0 if game.winner == actor else -1.0
+            dones[i] = game.state == "GAME_OVER"
+            winners.append(game.winner)
+            if dones[i] and self.auto_reset:
+                self.games[i] = SatellitesGame(headless=True)
+        return rewards, dones, winners
+
+    def step(self, actions: Sequence[int]) -> np.ndarray:
+        """Apply one action index per env and return the done flags."""
+        _, dones, _ = self.step_full(actions)
+        return dones
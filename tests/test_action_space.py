@@ -0,0 +1,255 @@
+import numpy as np
+import pytest
+
+from engine import SatellitesGame
+from rl.action_space import GlobalActionSpace, edge_list, normalize_visit_counts
+
+
+def test_argmax_legal_ignores_illegal_actions() -> None:
+    game = SatellitesGame(headless=True)
+    action_space = GlobalActionSpace(game)
+    legal = action_space.legal_action_indices(game)
+    scores = np.zeros(action_space.size, dtype=np.float32)
+    illegal = next(i for i in range(action_space.size) if i not in legal)
+    scores[illegal] = 100.0
+    scores[legal[-1]] = 1.0
+
+    assert action_space.argmax_legal(game, scores) == legal[-1]
+
+
+def test_argmax_legal_breaks_ties_towards_lowest_index() -> None:
+    game = SatellitesGame(headless=True)
+    action_space = GlobalActionSpace(game)
+    scores = np.ones(action_space.size, dtype=np.float32)
+
+    assert action_space.argmax_legal(game, scores) == min(action_space.legal_action_indices(game))
+
+
+def test_argmax_legal_returns_none_when_game_is_over() -> None:
+    game = SatellitesGame(headless=True)
+    game.state = "GAME_OVER"
+    action_space = GlobalActionSpace(game)
+
+    assert action_space.argmax_legal(game, np.zeros(action_space.size, dtype=np.float32)) is None
+
+
+def test_normalize_visit_counts_scatters_and_applies_temperature() -> None:
+    pi = normalize_visit_counts([(1, 1), (3, 3)], 5, temperature=1.0)
+    assert pi.shape == (5,)
+    assert np.allclose(pi, [0.0, 0.25, 0.0, 0.75, 0.0])
+
+    sharp = normalize_visit_counts([(1, 1), (3, 3)], 5, temperature=0.5)
+    assert np.allclose(sharp, [0.0, 0.1, 0.0, 0.9, 0.0])
+
+    greedy = normalize_visit_counts([(1, 1), (3, 3)], 5, temperature=0.0)
+    assert np.allclose(greedy, [0.0, 0.0, 0.0, 1.0, 0.0])
+
+
+def test_legal_masks_into_fills_preallocated_buffer() -> None:
+    games = [SatellitesGame(headless=True), SatellitesGame(headless=True)]
+    games[1].state = "GAME_OVER"
+    action_space = GlobalActionSpace(games[0])
+    out = np.ones((2, action_space.size), dtype=np.bool_)
+
+    result = action_space.legal_masks_into(games, out)
+
+    assert result is out
+    assert np.array_equal(out[0], action_space.legal_action_mask(games[0]))
+    assert not out[1].any()
+
+
+def test_legal_masks_into_rejects_wrong_shape_or_dtype() -> None:
+    games = [SatellitesGame(headless=True)]
+    action_space = GlobalActionSpace(games[0])
+
+    with pytest.raises(ValueError):
+        action_space.legal_masks_into(games, np.zeros((2, action_space.size), dtype=np.bool_))
+    with pytest.raises(ValueError):
+        action_space.legal_masks_into(games, np.zeros((1, action_space.size), dtype=np.float32))
+
+
+def test_legal_actions_filtered_keeps_candidate_order() -> None:
+    game = SatellitesGame(headless=True)
+    action_space = GlobalActionSpace(game)
+    legal = action_space.legal_action_indices(game)
+    illegal = next(i for i in range(action_space.size) if i not in legal)
+
+    filtered = action_space.legal_actions_filtered(game, [legal[-1], illegal, legal[0], legal[-1]])
+
+    assert filtered == [legal[-1], legal[0]]
+
+
+def test_add_and_move_index_match_action_table() -> None:
+    action_space = GlobalActionSpace(SatellitesGame(headless=True))
+
+    assert action_space.from_index(action_space.add_index(4, 5)) == ("add", 4, 5)
+    assert action_space.from_index(action_space.move_index((4, 5), (4, 6), 3)) == ("move", (4, 5), (4, 6), 3)
+    with pytest.raises(ValueError):
+        action_space.move_index((4, 5), (6, 5), 1)
+    with pytest.raises(ValueError):
+        action_space.move_index((4, 5), (4, 6), action_space.max_move_amount + 1)
+    with pytest.raises(ValueError):
+        action_space.add_index(9, 0)
+
+
+def test_legal_actions_decoded_matches_indices() -> None:
+    game = SatellitesGame(headless=True)
+    action_space = GlobalActionSpace(game)
+    game.apply_action(game.legal_actions()[0])
+    game.apply_action(("set_direction", True))
+
+    decoded = action_space.legal_actions_decoded(game)
+
+    assert sorted(decoded) == sorted(game.legal_actions())
+    assert [action_space.to_index(a) for a in decoded] == sorted(action_space.legal_action_indices(game))
+
+
+def test_apply_move_and_add_validate_phase_and_amount() -> None:
+    game = SatellitesGame(headless=True)
+    action_space = GlobalActionSpace(game, max_move_amount=1)
+    assert not action_space.apply_add(game, 4, 5)
+
+    game.add_unit(4, 5, 0, "bot", 2)
+    game.state = "PERFORM_ACTIONS"
+    game.action_type = "move_bot"
+    game.actions_remaining = 2
+    assert not action_space.apply_move(game, (4, 5), (4, 6), 2)
+    assert action_space.apply_move(game, (4, 5), (4, 6), 1)
+    assert game.grid[(4, 6)]["count"] == 1
+    assert game.actions_remaining == 1
+
+
+def test_action_tables_are_shared_per_max_move_amount() -> None:
+    first = GlobalActionSpace(max_move_amount=5)
+    second = GlobalActionSpace(max_move_amount=5)
+    other = GlobalActionSpace(max_move_amount=6)
+
+    assert first.index_to_action is second.index_to_action
+    assert first.action_to_index is second.action_to_index
+    with pytest.raises(TypeError):
+        first.action_to_index[("pass",)] = 0
+    with pytest.raises(AttributeError):
+        first.index_to_action.append(("pass",))
+    assert other.size > first.size
+    assert first.from_index(first.move_index((4, 5), (4, 6), 5)) == ("move", (4, 5), (4, 6), 5)
+
+
+def test_legal_action_mask_agrees_with_indices() -> None:
+    game = SatellitesGame(headless=True)
+    action_space = GlobalActionSpace(game)
+    game.apply_action(game.legal_actions()[0])
+    game.apply_action(("set_direction", False))
+
+    mask = action_space.legal_action_mask(game)
+
+    assert mask.shape == (action_space.size,) and mask.dtype == np.bool_
+    assert np.flatnonzero(mask).tolist() == sorted(action_space.legal_action_indices(game))
+
+
+def test_describe_covers_each_action_kind() -> None:
+    action_space = GlobalActionSpace(SatellitesGame(headless=True))
+
+    assert action_space.describe(2) == "choose satellite slot 2"
+    assert action_space.describe(7) == "distribute clockwise"
+    assert action_space.describe(action_space.add_index(4, 5)) == "add at (4, 5)"
+    assert action_space.describe(action_space.move_index((4, 5), (4, 6), 3)) == "move 3 from (4, 5) to (4, 6)"
+    assert action_space.describe(action_space.size).startswith("invalid")
+    assert action_space.describe(-1).startswith("invalid")
+
+
+def test_move_index_follows_edge_major_layout() -> None:
+    game = SatellitesGame(headless=True)
+    action_space = GlobalActionSpace(game, max_move_amount=4)
+    move_base = 6 + 2 + game.num_cells
+    src = game.cell_id_to_coord[0]
+    first, second = game.neighbors_by_cell_id[0][:2]
+
+    assert action_space.move_index(src, first, 1) == move_base
+    assert action_space.move_index(src, first, 4) == move_base + 3
+    assert action_space.move_index(src, second, 1) == move_base + 4
+
+
+def test_mirror_index_maps_legal_actions_to_mirrored_game() -> None:
+    game = SatellitesGame(headless=True)
+    action_space = GlobalActionSpace(game)
+    game.apply_action(game.legal_actions()[0])
+    game.apply_action(("set_direction", True))
+    mirrored = game.mirror()
+
+    legal = action_space.legal_action_indices(game)
+    assert sorted(action_space.mirror_index(i) for i in legal) == sorted(action_space.legal_action_indices(mirrored))
+    assert all(action_space.mirror_index(action_space.mirror_index(i)) == i for i in range(action_space.size))
+
+
+def test_apply_logit_mask_writes_neg_inf_in_place() -> None:
+    game = SatellitesGame(headless=True)
+    action_space = GlobalActionSpace(game)
+    logits = np.arange(action_space.size, dtype=np.float32)
+
+    out = action_space.apply_logit_mask(game, logits)
+
+    legal = action_space.legal_action_indices(game)
+    assert out is logits
+    assert np.flatnonzero(np.isfinite(logits)).tolist() == sorted(legal)
+    assert all(logits[i] == i for i in legal)
+    with pytest.raises(ValueError):
+        action_space.apply_logit_mask(game, np.zeros(3, dtype=np.float32))
+
+
+def test_edge_list_orders_move_blocks() -> None:
+    game = SatellitesGame(headless=True)
+    action_space = GlobalActionSpace(game, max_move_amount=3)
+
+    assert action_space.edges is edge_list(game)
+    assert isinstance(action_space.edges, tuple)
+    assert len(action_space.edges) == game.num_edges()
+    assert action_space.size == action_space.move_base + len(action_space.edges) * 3
+    for ordinal in (0, 17, len(action_space.edges) - 1):
+        src, dst = action_space.edge_endpoints(ordinal)
+        index = action_space.move_base + ordinal * 3
+        assert action_space.from_index(index) == ("move", src, dst, 1)
+
+
+def test_every_move_index_roundtrips_through_its_edge() -> None:
+    game = SatellitesGame(headless=True)
+    action_space = GlobalActionSpace(game, max_move_amount=2)
+
+    for index in range(action_space.move_base, action_space.size):
+        _, src, dst, amount = action_space.from_index(index)
+        assert action_space.move_index(src, dst, amount) == index
+
+
+def test_step_rewards_captures_and_penalizes_illegal_actions() -> None:
+    game = SatellitesGame(headless=True)
+    action_space = GlobalActionSpace(game)
+    game.grid = {(4, 5): {"owner": 0, "type": "bot", "count": 2}}
+    game.turn = 0
+    game.state = "PERFORM_ACTIONS"
+    game.action_type = "move_bot"
+    game.actions_remaining = 2
+    before = game.to_bytes()
+
+    illegal = action_space.move_index((4, 5), (4, 6), 3)
+    assert action_space.step(game, illegal, illegal_penalty=-0.5) == (-0.5, False)
+    assert game.to_bytes() == before
+
+    assert action_space.step(game, action_space.move_index((4, 5), (4, 4), 2)) == (2.0, False)
+    assert action_space.step(game, action_space.move_index((4, 4), (4, 5), 1)) == (0.0, False)
+
+
+def test_classify_index_separates_range_phase_and_legality() -> None:
+    game = SatellitesGame(headless=True)
+    action_space = GlobalActionSpace(game)
+    game.grid = {(4, 5): {"owner": 0, "type": "bot", "count": 2}}
+    game.turn = 0
+    game.state = "PERFORM_ACTIONS"
+    game.action_type = "move_bot"
+    game.actions_remaining = 2
+
+    assert action_space.classify_index(game, action_space.move_index((4, 5), (4, 4), 2)) == "legal"
+    assert action_space.classify_index(game, action_space.move_index((4, 5), (4, 4), 3)) == "illegal"
+    assert action_space.classify_index(game, action_space.add_index(4, 5)) == "illegal"
+    assert action_space.classify_index(game, action_space.to_index(("select_satellite", 0))) == "wrong_phase"
+    for index in (-1, action_space.size):
+        with pytest.raises(ValueError, match="outside"):
+            action_space.classify_index(game, index)
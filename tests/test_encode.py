@@ -0,0 +1,142 @@
+import pytest
+
+np = pytest.importorskip("numpy")
+
+from engine import SatellitesGame
+from rl.encode import FeatureEncoder, feature_dim, feature_layout
+
+
+def test_encode_batch_matches_per_state_encoding() -> None:
+    games = [SatellitesGame(headless=True) for _ in range(3)]
+    games[1].scores = [2, 1]
+    games[2].apply_action(games[2].legal_actions()[0])
+    enc = FeatureEncoder(games[0])
+
+    batch = enc.encode_batch(games)
+
+    assert batch.shape == (3, enc.feature_dim)
+    for row, game in enumerate(games):
+        assert np.array_equal(batch[row], enc.encode(game))
+    out = np.full((3, enc.feature_dim), 7.0, dtype=np.float32)
+    assert enc.encode_batch(games, out=out) is out
+    assert np.array_equal(out, batch)
+    with pytest.raises(ValueError):
+        enc.encode_batch(games, out=np.zeros((2, enc.feature_dim), dtype=np.float32))
+
+
+def test_feature_layout_tiles_feature_dim() -> None:
+    game = SatellitesGame(headless=True)
+    enc = FeatureEncoder(game)
+    layout = feature_layout(game.num_cells)
+
+    assert feature_dim(game.num_cells) == enc.feature_dim == enc.encode(game).shape[0]
+    assert layout[0] == ("cells", 0, game.num_cells * enc.cell_feature_size)
+    for (_, offset, length), (_, next_offset, _) in zip(layout, layout[1:]):
+        assert offset + length == next_offset
+    assert layout[-1][1] + layout[-1][2] == enc.feature_dim
+    assert layout[1][0] == "side_to_move"
+    assert enc.encode(game)[layout[1][1] + game.turn] == 1.0
+
+
+def test_encode_planes_places_cells_by_row_and_column() -> None:
+    game = SatellitesGame(headless=True)
+    game.scores = [3, 0]
+    enc = FeatureEncoder(game)
+
+    planes = enc.encode_planes(game)
+    feat = enc.encode(game)
+
+    assert planes.shape == enc.plane_shape == (enc.cell_feature_size + enc.global_feature_size, 9, 12)
+    size = enc.cell_feature_size
+    tank_cell = game.coord_to_cell_id[(0, 4)]
+    assert planes[1, 0, 4] == feat[tank_cell * size + 1]
+    assert planes[4, 4, 4] == 1.0
+    score_channel = size + 2
+    assert planes[score_channel, 8, 7] == feat[game.num_cells * size + 2]
+    assert planes[score_channel, 0, 11] == 0.0
+
+
+def test_encode_delta_patches_previous_features() -> None:
+    game = SatellitesGame(headless=True)
+    enc = FeatureEncoder(game)
+    prev = game.clone()
+    game.apply_action(game.legal_actions()[0])
+    game.apply_action(("set_direction", True))
+
+    feat = enc.encode(prev)
+    delta = enc.encode_delta(prev, game)
+    for idx, value in delta:
+        feat[idx] = value
+
+    assert delta
+    assert feat.tobytes() == enc.encode(game).tobytes()
+    assert enc.encode_delta(game, game.clone()) == []
+
+
+def test_encode_delta_covers_artefact_capture_neighbors() -> None:
+    game = SatellitesGame(headless=True)
+    game.grid = {(4, 5): {"owner": 0, "type": "bot", "count": 2}}
+    game.turn = 0
+    game.state = "PERFORM_ACTIONS"
+    game.action_type = "move_bot"
+    game.actions_remaining = 2
+    enc = FeatureEncoder(game)
+    prev = game.clone()
+    assert game.try_move((4, 5), (4, 4), 2)
+
+    feat = enc.encode(prev)
+    for idx, value in enc.encode_delta(prev, game):
+        feat[idx] = value
+
+    assert feat.tobytes() == enc.encode(game).tobytes()
+
+
+def test_encoder_decode_roundtrips_encoded_state() -> None:
+    game = SatellitesGame(headless=True)
+    game.scores = [4, 2]
+    game.apply_action(game.legal_actions()[0])
+    enc = FeatureEncoder(game)
+
+    decoded = enc.decode(enc.encode(game))
+
+    assert decoded.grid == game.grid
+    assert decoded.artefacts == game.artefacts
+    assert decoded.scores == game.scores
+    assert decoded.turn == game.turn
+    assert decoded.state == game.state
+    assert decoded.active_satellite_idx == game.active_satellite_idx
+    assert decoded.satellites == game.satellites
+    assert np.array_equal(enc.encode(decoded), enc.encode(game))
+
+
+def test_encoder_scales_follow_game_rules_and_overrides() -> None:
+    game = SatellitesGame(headless=True)
+    game.WIN_SCORE = 5
+    game.scores = [4, 2]
+    enc = FeatureEncoder(game)
+    offset = {name: off for name, off, _ in feature_layout(enc.num_cells)}["scores"]
+
+    obs = enc.encode(game)
+    assert obs.shape == (enc.feature_dim,)
+    assert obs[offset] == pytest.approx(4 / 5)
+    assert enc.decode(obs).scores == [4, 2]
+
+    fixed = FeatureEncoder(game, score_scale=10.0, unit_scale=40.0)
+    assert fixed.encode(game)[offset] == pytest.approx(0.4)
+    assert fixed.decode(fixed.encode(game)).grid == game.grid
+
+
+def test_encoder_marks_cells_adjacent_to_artefacts() -> None:
+    game = SatellitesGame(headless=True)
+    enc = FeatureEncoder(game)
+    size = enc.cell_feature_size
+
+    obs = enc.encode(game)
+    adjacent = game.coord_to_cell_id[(4, 5)]  # next to the (4, 4) artefact
+    far = game.coord_to_cell_id[(0, 0)]
+    assert obs[adjacent * size + 7] == 1.0
+    assert obs[far * size + 7] == 0.0
+
+    game.artefacts.remove((4, 4))
+    game.is_artefact_cell[game.coord_to_cell_id[(4, 4)]] = False
+    assert enc.encode(game)[game.coord_to_cell_id[(3, 4)] * size + 7] == 0.0
@@ -1,4 +1,8 @@
-from engine import SatellitesGame
+import random
+
+import pytest
+
+from engine import ActionType, InvalidStateError, Phase, SatellitesGame, rotate_charges
 from agents.mcts import MCTS, SatellitesAdapter
 
 
@@ -9,6 +13,13 @@ def _prep_add_tank(game: SatellitesGame, turn: int = 0) -> None:
     game.actions_remaining = 1
 
 
+def _prep_move(game: SatellitesGame, unit_type: str, actions_remaining: int = 1, turn: int = 0) -> None:
+    game.turn = turn
+    game.state = "PERFORM_ACTIONS"
+    game.action_type = f"move_{unit_type}"
+    game.actions_remaining = actions_remaining
+
+
 def test_win_threshold_is_nine_points() -> None:
     game = SatellitesGame(headless=True)
     game.turn = 0
@@ -17,6 +28,30 @@ def test_win_threshold_is_nine_points() -> None:
     assert game.check_win() is True
     assert game.state == "GAME_OVER"
     assert game.winner == 0
+    assert game.end_reason == "score_limit"
+
+
+def test_compute_winner_does_not_mutate() -> None:
+    game = SatellitesGame(headless=True)
+    assert game.compute_winner() is None
+
+    game.turn = 1
+    game.scores = [2, 9]
+    assert game.compute_winner() == 1
+    assert game.state == "CHOOSE_SATELLITE"
+    assert game.winner is None
+
+    game.scores = [3, 3]
+    game.artefacts = []
+    assert game.compute_winner() == 1
+    assert game.winner is None
+
+
+def test_get_scores_returns_both_players() -> None:
+    game = SatellitesGame(headless=True)
+    game.scores = [4, 7]
+
+    assert game.get_scores() == (4, 7)
 
 
 def test_tank_attack_does_not_move_into_target_hex() -> None:
@@ -38,6 +73,46 @@ def test_tank_attack_does_not_move_into_target_hex() -> None:
     assert game.grid[(4, 4)]["count"] == 2
 
 
+def test_captures_count_destroyed_and_lost_units() -> None:
+    game = SatellitesGame(headless=True)
+    game.grid = {
+        (4, 4): {"owner": 0, "type": "tank", "count": 3},
+        (4, 5): {"owner": 1, "type": "bot", "count": 2},
+        (3, 4): {"owner": 1, "type": "tank", "count": 1},
+    }
+    game.turn = 0
+    game.actions_remaining = 3
+
+    game.execute_move((4, 4), (4, 5), 3)
+    game.execute_move((4, 4), (3, 4), 2)
+
+    assert game.captures(0) == (3, 0)
+    assert game.captures(1) == (0, 3)
+
+
+def test_unit_cap_blocks_adds_at_twenty() -> None:
+    game = SatellitesGame(headless=True)
+    _prep_add_tank(game, turn=0)
+    game.grid = {(4, 5): {"owner": 0, "type": "tank", "count": 20}}
+
+    assert game.legal_actions() == []
+    assert game.execute_add(4, 6) is False
+    assert "Unit Cap" in game.info_message
+
+
+def test_unit_cap_zero_means_unlimited() -> None:
+    game = SatellitesGame(headless=True)
+    game.UNIT_CAP = 0
+    _prep_add_tank(game, turn=0)
+    game.actions_remaining = 3
+    game.grid = {(4, 5): {"owner": 0, "type": "tank", "count": 40}}
+
+    assert game.legal_actions()
+    assert game.execute_add(4, 5) is True
+    assert game.execute_add(4, 6) is True
+    assert game.get_player_unit_count(0) == 42
+
+
 def test_tank_drop_allows_empty_non_opponent_start_hex() -> None:
     game = SatellitesGame(headless=True)
     _prep_add_tank(game, turn=0)
@@ -111,6 +186,37 @@ def test_precomputed_distances_are_symmetric_and_zero_diagonal() -> None:
     assert game.get_hex_distance((0, 3), (8, 4)) > 0
 
 
+def test_nearest_artefact_uses_hex_distance() -> None:
+    game = SatellitesGame(headless=True)
+    cid = game.coord_to_cell_id
+
+    assert game.nearest_artefact(cid[(4, 4)]) == (cid[(4, 4)], 0)
+    assert game.nearest_artefact(cid[(4, 5)]) == (cid[(4, 4)], 1)
+    assert game.nearest_artefact(0) == (cid[(2, 1)], game.get_hex_distance((0, 0), (2, 1)))
+    with pytest.raises(ValueError):
+        game.nearest_artefact(game.num_cells)
+
+    game.artefacts = []
+    assert game.nearest_artefact(cid[(4, 5)]) is None
+
+
+def test_empty_regions_split_on_occupied_cells() -> None:
+    game = SatellitesGame(headless=True)
+    game.grid = {}
+    assert len(game.empty_regions()) == 1
+    assert len(game.empty_regions()[0]) == game.num_cells
+
+    # Wall off the (0, 0) corner.
+    game.grid = {
+        coord: {"owner": 0, "type": "tank", "count": 1}
+        for coord in ((0, 1), (1, 0), (1, 1))
+    }
+    regions = game.empty_regions()
+    assert regions[0] == [game.coord_to_cell_id[(0, 0)]]
+    assert len(regions) == 2
+    assert len(regions[1]) == game.num_cells - 4
+
+
 def test_cache_rebuilds_after_external_grid_assignment() -> None:
     game = SatellitesGame(headless=True)
     game.grid = {
@@ -193,6 +299,21 @@ def test_legal_actions_choose_satellite_and_direction() -> None:
     assert dir_actions == [("set_direction", False), ("set_direction", True)]
 
 
+def test_legal_counts_by_type_partition_legal_actions() -> None:
+    game = SatellitesGame(headless=True)
+    assert game.legal_counts_by_type() == (4, 0, 0, 0)
+
+    game.apply_action(game.legal_actions()[0])
+    assert game.legal_counts_by_type() == (0, 2, 0, 0)
+
+    game.grid = {(4, 5): {"owner": 0, "type": "tank", "count": 3}}
+    game.state = "PERFORM_ACTIONS"
+    game.action_type = "move_tank"
+    game.actions_remaining = 1
+    assert game.legal_counts_by_type() == (0, 0, 0, len(game.legal_actions()))
+    assert game.legal_counts_by_type(max_move_amount=1) == (0, 0, 0, len(game.legal_moves(max_move_amount=1)))
+
+
 def test_legal_actions_perform_add_only_returns_valid_moves() -> None:
     game = SatellitesGame(headless=True)
     _prep_add_tank(game, turn=0)
@@ -204,6 +325,20 @@ def test_legal_actions_perform_add_only_returns_valid_moves() -> None:
     assert ("add", 4, 4) not in actions  # artefact
 
 
+def test_legal_add_cells_lists_placement_cell_ids() -> None:
+    game = SatellitesGame(headless=True)
+    assert game.legal_add_cells() == []
+
+    _prep_add_tank(game, turn=0)
+    cells = game.legal_add_cells()
+    assert cells == [game.coord_to_cell_id[(a[1], a[2])] for a in game.legal_actions()]
+    assert cells == sorted(cells)
+    assert game.coord_to_cell_id[(4, 4)] not in cells
+
+    game.action_type = "add_bot"
+    assert game.legal_add_cells() == [game.coord_to_cell_id[(0, 3)]]
+
+
 def test_satellites_adapter_smoke() -> None:
     game = SatellitesGame(headless=True)
     adapter = SatellitesAdapter()
@@ -214,6 +349,25 @@ def test_satellites_adapter_smoke() -> None:
     assert next_state is not None
 
 
+def test_seeded_rng_users_repeat_for_equal_seeds() -> None:
+    orders = {
+        seed: [sat["type"] for sat in SatellitesGame(headless=True, seed=seed).satellites]
+        for seed in range(8)
+    }
+    assert all(
+        [sat["type"] for sat in SatellitesGame(headless=True, seed=seed).satellites] == order
+        for seed, order in orders.items()
+    )
+    assert len({tuple(order) for order in orders.values()}) > 1
+
+    game = SatellitesGame(headless=True, seed=3)
+    picks = [
+        MCTS(SatellitesAdapter(), iterations=10, rollout_depth=6, seed=5).select_action(game.clone())[0]
+        for _ in range(2)
+    ]
+    assert picks[0] == picks[1]
+
+
 def test_mcts_smoke_selects_legal_action() -> None:
     game = SatellitesGame(headless=True)
     adapter = SatellitesAdapter()
@@ -250,3 +404,892 @@ def test_state_key_changes_with_state_mutation() -> None:
     game.scores[0] += 1
     k1 = adapter.state_key(game)
     assert k0 != k1
+
+
+def test_halfmove_clock_counts_actions_and_resets_on_capture() -> None:
+    game = SatellitesGame(headless=True)
+    game.grid = {
+        (4, 4): {"owner": 0, "type": "tank", "count": 2},
+        (4, 5): {"owner": 1, "type": "bot", "count": 1},
+    }
+    _prep_move(game, "tank", 3)
+
+    assert game.apply_action(("move", (4, 4), (3, 4), 1)) is True
+    assert game.halfmove_clock == 1
+
+    assert game.apply_action(("move", (4, 4), (4, 5), 1)) is True
+    assert game.halfmove_clock == 0
+
+
+def test_halfmove_clock_limit_declares_draw_at_end_of_turn() -> None:
+    game = SatellitesGame(headless=True)
+    game.MAX_HALFMOVE_CLOCK = 3
+    game.scores = [2, 0]
+    game.grid = {(4, 5): {"owner": 0, "type": "tank", "count": 1}}
+    game.halfmove_clock = 2
+    _prep_move(game, "tank")
+
+    assert game.apply_action(("move", (4, 5), (4, 6), 1)) is True
+
+    assert game.state == "GAME_OVER"
+    assert game.winner == -1
+
+
+def test_fully_discharged_ring_ends_game_at_turn_start() -> None:
+    game = SatellitesGame(headless=True)
+    for sat in game.satellites:
+        sat["charges"] = 0
+    game.scores = [3, 1]
+    _prep_move(game, "tank", 0)
+
+    game.end_turn()
+
+    assert game.state == "GAME_OVER"
+    assert game.winner == 0
+    assert game.legal_actions() == []
+
+
+def test_check_satellites_charged_keeps_playable_state() -> None:
+    game = SatellitesGame(headless=True)
+    game.check_satellites_charged()
+    assert game.state == "CHOOSE_SATELLITE"
+
+    for sat in game.satellites:
+        sat["charges"] = 0
+    game.check_satellites_charged()
+    assert game.state == "GAME_OVER"
+    assert game.winner == -1
+
+
+def test_last_changed_cells_tracks_moves_shots_and_adds() -> None:
+    game = SatellitesGame(headless=True)
+    game.grid = {
+        (4, 4): {"owner": 0, "type": "tank", "count": 2},
+        (4, 5): {"owner": 1, "type": "bot", "count": 1},
+    }
+    _prep_move(game, "tank", 3)
+    cid = game.coord_to_cell_id
+
+    game.apply_action(("move", (4, 4), (4, 5), 2))
+    assert game.last_changed_cells == [cid[(4, 5)]]
+
+    game.apply_action(("move", (4, 4), (3, 4), 1))
+    assert game.last_changed_cells == [cid[(4, 4)], cid[(3, 4)]]
+
+    game.apply_action(("move", (4, 4), (8, 4), 1))
+    assert game.last_changed_cells == []
+
+    _prep_add_tank(game, turn=0)
+    game.apply_action(("add", 5, 5))
+    assert game.last_changed_cells == [cid[(5, 5)]]
+
+
+def test_predict_move_reports_effect_without_mutating() -> None:
+    game = SatellitesGame(headless=True)
+    game.grid = {
+        (4, 4): {"owner": 0, "type": "tank", "count": 2},
+        (4, 5): {"owner": 1, "type": "bot", "count": 3},
+        (2, 2): {"owner": 0, "type": "bot", "count": 2},
+    }
+    _prep_move(game, "tank", 2)
+    before = game.clone()
+
+    shot = game.predict_move((4, 4), (4, 5), 1)
+    assert shot == {
+        "legal": True,
+        "src_count": 2,
+        "dst_count": 0,
+        "dst_owner": -1,
+        "capture": True,
+        "units_destroyed": 3,
+        "score_gain": 0,
+    }
+    assert game.predict_move((4, 4), (4, 4), 1) == {"legal": False}
+    assert game.grid == before.grid
+    assert game.actions_remaining == before.actions_remaining
+
+    game.action_type = "move_bot"
+    grab = game.predict_move((2, 2), (2, 1), 2)
+    assert grab["legal"] is True
+    assert grab["score_gain"] == 2
+    assert grab["dst_count"] == 2 and grab["dst_owner"] == 0
+    assert (2, 1) in game.artefacts
+
+
+def test_rotate_charges_wraps_around_ring_in_both_directions() -> None:
+    charges = [0, 1, 0, 0, 2, 0]
+
+    assert rotate_charges(charges, 4, 3, 1) == [1, 2, 0, 0, 2, 1]
+    assert rotate_charges(charges, 1, 3, -1) == [1, 1, 0, 0, 3, 1]
+    assert rotate_charges(charges, 0, 7, 1) == [1, 3, 1, 1, 3, 1]
+    assert charges == [0, 1, 0, 0, 2, 0]
+
+
+def test_all_legal_moves_covers_both_kinds() -> None:
+    game = SatellitesGame(headless=True)
+
+    moves = game.all_legal_moves()
+
+    assert game.state == "CHOOSE_SATELLITE"
+    assert {kind for kind, _, _, _ in moves} == {"tank", "bot"}
+    assert all(start == (0, 4) for kind, start, _, _ in moves if kind == "tank")
+    assert all(start == (0, 3) for kind, start, _, _ in moves if kind == "bot")
+    assert all(amount <= 1 for _, _, _, amount in game.all_legal_moves(max_move_amount=1))
+
+
+def test_move_scores_artefact_matches_capture_rule() -> None:
+    game = SatellitesGame(headless=True)
+    game.grid = {
+        (3, 1): {"owner": 0, "type": "bot", "count": 2},
+        (4, 5): {"owner": 0, "type": "tank", "count": 2},
+    }
+    _prep_move(game, "bot")
+
+    assert game.move_scores_artefact((3, 1), (2, 1), 1) is True
+    assert game.move_scores_artefact((3, 1), (3, 2), 1) is False
+    assert game.move_scores_artefact((3, 1), (2, 1), 3) is False
+
+    game.action_type = "move_tank"
+    assert game.move_scores_artefact((4, 5), (4, 4), 1) is False
+
+
+def test_legal_moves_follow_action_type_and_clamp_amount() -> None:
+    game = SatellitesGame(headless=True)
+    game.grid = {
+        (4, 5): {"owner": 0, "type": "tank", "count": 3},
+        (2, 2): {"owner": 0, "type": "bot", "count": 1},
+    }
+    _prep_move(game, "tank")
+
+    moves = game.legal_moves(max_move_amount=2)
+    assert moves
+    assert all(start == (4, 5) and amount <= 2 for start, _, amount in moves)
+    assert len(game.legal_moves()) == len(game.legal_actions())
+
+    game.action_type = "move_bot"
+    assert all(start == (2, 2) for start, _, _ in game.legal_moves())
+
+    game.action_type = "add_bot"
+    assert game.legal_moves() == []
+
+
+def test_max_move_amounts_per_source_cell() -> None:
+    game = SatellitesGame(headless=True)
+    game.grid = {
+        (4, 5): {"owner": 0, "type": "tank", "count": 3},
+        (2, 2): {"owner": 0, "type": "bot", "count": 2},
+    }
+    _prep_move(game, "tank")
+    cid = game.coord_to_cell_id
+
+    amounts = game.max_move_amounts()
+    assert len(amounts) == game.num_cells
+    assert amounts[cid[(4, 5)]] == 3
+    assert amounts[cid[(2, 2)]] == 0
+    assert sum(1 for a in amounts if a) == 1
+    assert game.max_move_amounts(max_move_amount=2)[cid[(4, 5)]] == 2
+
+
+def test_neighbor_order_is_pinned() -> None:
+    game = SatellitesGame(headless=True)
+
+    # Corner, centre, middle-row ends, and a lower-half cell.
+    assert game.get_hex_neighbors(0, 0) == [(0, 1), (1, 0), (1, 1)]
+    assert game.get_hex_neighbors(4, 5) == [(4, 4), (4, 6), (3, 4), (3, 5), (5, 4), (5, 5)]
+    assert game.get_hex_neighbors(4, 0) == [(4, 1), (3, 0), (5, 0)]
+    assert game.get_hex_neighbors(4, 11) == [(4, 10), (3, 10), (5, 10)]
+    assert game.get_hex_neighbors(5, 3) == [(5, 2), (5, 4), (4, 3), (4, 4), (6, 2), (6, 3)]
+    assert game.get_hex_neighbors(8, 7) == [(8, 6), (7, 7), (7, 8)]
+
+
+def test_start_cells_come_from_start_masks() -> None:
+    game = SatellitesGame(headless=True)
+    assert game.start_cells(0) == [(0, 3), (0, 4)]
+    assert game.start_cells(1) == [(8, 3), (8, 4)]
+
+    game.grid = {(7, 3): {"owner": 0, "type": "tank", "count": 1}}
+    _prep_move(game, "tank")
+    assert ("move", (7, 3), (8, 3), 1) not in game.legal_actions()
+    assert ("move", (7, 3), (7, 2), 1) in game.legal_actions()
+
+    # Relocating the opponent's start cells moves the forbidden destinations with them.
+    game.is_p1_start_cell = [False] * game.num_cells
+    game.is_p1_start_cell[game.coord_to_cell_id[(7, 2)]] = True
+    assert ("move", (7, 3), (8, 3), 1) in game.legal_actions()
+    assert ("move", (7, 3), (7, 2), 1) not in game.legal_actions()
+
+
+def _finish_turn(game: SatellitesGame, actions_taken: int = 1) -> None:
+    game.state = "PERFORM_ACTIONS"
+    game.action_type = "move_tank"
+    game.actions_remaining = 0
+    game.turn_actions_taken = actions_taken
+    game.end_turn()
+
+
+def test_max_turns_zero_and_one_end_after_first_turn() -> None:
+    for limit in (0, 1):
+        game = SatellitesGame(headless=True)
+        game.MAX_TURNS = limit
+        assert game.state == "CHOOSE_SATELLITE"
+        assert game.legal_actions()
+
+        _finish_turn(game)
+
+        assert game.state == "GAME_OVER"
+        assert game.winner == -1
+        assert game.turn == 0
+
+
+def test_max_turns_two_allows_a_full_round_first() -> None:
+    game = SatellitesGame(headless=True)
+    game.MAX_TURNS = 2
+
+    _finish_turn(game)
+    assert game.state == "CHOOSE_SATELLITE" and game.turn == 1
+    _finish_turn(game)
+    assert game.state == "CHOOSE_SATELLITE" and game.turn == 0 and game.turn_count == 2
+    _finish_turn(game)
+    assert game.state == "GAME_OVER"
+
+
+def test_ply_counts_turns_while_turn_count_counts_rounds() -> None:
+    game = SatellitesGame(headless=True)
+    assert (game.ply, game.turn_count) == (0, 1)
+
+    _finish_turn(game)
+    assert (game.ply, game.turn_count) == (1, 1)
+    _finish_turn(game)
+    assert (game.ply, game.turn_count) == (2, 2)
+    _finish_turn(game)
+    assert (game.ply, game.turn_count) == (3, 2)
+
+
+def test_turns_remaining_counts_down_full_rounds() -> None:
+    game = SatellitesGame(headless=True)
+    game.MAX_TURNS = 3
+    assert game.turns_remaining() == 2
+
+    _finish_turn(game)
+    assert game.turns_remaining() == 2
+    _finish_turn(game)
+    assert game.turns_remaining() == 1
+
+
+def test_active_satellite_type_follows_selection() -> None:
+    game = SatellitesGame(headless=True)
+    assert game.active_satellite_type() is None
+
+    idx = game.legal_actions()[0][1]
+    game.apply_action(("select_satellite", idx))
+    assert game.active_satellite_type() == game.satellites[idx]["type"]
+
+
+def test_can_still_win_bounds_remaining_artefact_points() -> None:
+    game = SatellitesGame(headless=True)
+    assert game.can_still_win(0) and game.can_still_win(1)
+
+    game.UNIT_CAP = 2
+    game.artefacts = game.artefacts[:1]
+    game.scores = [1, 4]
+    assert not game.can_still_win(0)
+    assert game.can_still_win(1)
+
+    game.UNIT_CAP = 0
+    assert game.can_still_win(0)
+
+    game = SatellitesGame(headless=True, artefact_values={(2, 1): -5})
+    game.UNIT_CAP = 2
+    _set_artefacts(game, [(2, 1)])
+    game.scores = [1, 4]
+    assert not game.can_still_win(0)
+
+    game.state = "GAME_OVER"
+    game.winner = 1
+    assert not game.can_still_win(0)
+    assert game.can_still_win(1)
+
+
+def test_cell_predicates_follow_grid_and_check_bounds() -> None:
+    game = SatellitesGame(headless=True)
+    game.add_unit(4, 5, 1, "bot", 1)
+    cid = game.coord_to_cell_id[(4, 5)]
+    empty = game.coord_to_cell_id[(4, 6)]
+
+    assert not game.is_empty(cid) and game.is_owned_by(cid, 1)
+    assert not game.is_owned_by(cid, 0)
+    assert game.is_empty(empty) and not game.is_owned_by(empty, 1)
+    with pytest.raises(ValueError):
+        game.is_empty(game.num_cells)
+
+
+def test_final_result_only_once_game_is_over() -> None:
+    game = SatellitesGame(headless=True)
+    assert game.final_result() is None
+
+    game.scores = [2, 1]
+    for sat in game.satellites:
+        sat["charges"] = 0
+    game.check_satellites_charged()
+
+    assert game.final_result() == {
+        "winner": 0,
+        "scores": (2, 1),
+        "end_reason": "no_charges",
+        "turn_count": game.turn_count,
+        "ply": game.ply,
+    }
+
+
+def test_random_board_is_reproducible_per_seed() -> None:
+    first = SatellitesGame.random_board(4, 30, seed=11)
+    second = SatellitesGame.random_board(4, 30, seed=11)
+
+    assert first.to_bytes() == second.to_bytes()
+    assert len(first.artefacts) == 4 and first.MAX_TURNS == 30
+    assert sum(first.is_artefact_cell) == 4
+    for coord in first.artefacts:
+        assert coord not in first.grid
+        assert coord not in first.start_cells(0) + first.start_cells(1)
+    with pytest.raises(ValueError):
+        SatellitesGame.random_board(first.num_cells, 30, seed=11)
+
+
+def test_random_board_leaves_global_rng_alone() -> None:
+    state = random.getstate()
+
+    SatellitesGame.random_board(4, 30, seed=11)
+
+    assert random.getstate() == state
+
+
+def test_legal_amounts_bounds_tank_attack_range() -> None:
+    game = SatellitesGame(headless=True)
+    game.grid = {
+        (4, 5): {"owner": 0, "type": "tank", "count": 4},
+        (4, 6): {"owner": 1, "type": "tank", "count": 1},
+    }
+    _prep_move(game, "tank")
+
+    assert game.legal_amounts((4, 5), (4, 6)) == (2, 4)
+    assert game.legal_amounts((4, 5), (4, 6), max_move_amount=3) == (2, 3)
+    assert game.legal_amounts((4, 5), (6, 5)) == (0, 0)
+
+
+def test_undo_turn_rewinds_to_satellite_choice() -> None:
+    game = SatellitesGame(headless=True)
+    tokens = []
+    assert not game.undo_turn(tokens)
+
+    while game.turn == 0:
+        tokens.append(game.apply_action_with_undo(game.legal_actions()[0])[1])
+    first_turn = len(tokens)
+    start = game.to_bytes()
+    tokens.append(game.apply_action_with_undo(game.legal_actions()[0])[1])
+    assert game.state == "CHOOSE_DIRECTION"
+
+    assert game.undo_turn(tokens)
+    assert game.to_bytes() == start
+    assert len(tokens) == first_turn
+    assert not game.undo_turn(tokens)
+
+
+def test_board_summary_counts_cells_units_and_charges() -> None:
+    game = SatellitesGame(headless=True)
+    game.add_unit(4, 5, 1, "bot", 3)
+
+    assert game.board_summary() == {
+        "empty_cells": game.num_cells - 5,
+        "stacks": (2, 3),
+        "units": (4, 7),
+        "artefacts": 6,
+        "total_charges": 8,
+        "scores": (0, 0),
+    }
+
+
+def test_stacks_sorted_largest_first() -> None:
+    game = SatellitesGame(headless=True)
+    game.add_unit(4, 5, 0, "tank", 5)
+    cell = game.coord_to_cell_id
+
+    assert game.stacks(0) == [(cell[(4, 5)], 2, 5), (cell[(0, 3)], 1, 2), (cell[(0, 4)], 2, 2)]
+    assert [s[0] for s in game.stacks(1)] == [cell[(8, 3)], cell[(8, 4)]]
+
+
+def test_perform_action_kind_known_before_perform_phase() -> None:
+    game = SatellitesGame(headless=True)
+    assert game.perform_action_kind() is None
+
+    idx = next(i for i, sat in enumerate(game.satellites) if sat["type"] == "move_bot")
+    game.apply_action(("select_satellite", idx))
+    assert game.state == "CHOOSE_DIRECTION"
+    assert game.perform_action_kind() == ("move", "bot")
+
+
+def test_artefact_value_scales_capture_score() -> None:
+    game = SatellitesGame(headless=True, artefact_values={(4, 4): 3})
+    game.grid = {(4, 5): {"owner": 0, "type": "bot", "count": 2}}
+    _prep_move(game, "bot", 2)
+
+    assert game.try_move((4, 5), (4, 4), 2)
+    assert game.scores == [6, 0]
+    assert game.artefact_value[game.coord_to_cell_id[(2, 1)]] == 1
+
+
+def test_reachable_artefacts_uses_hop_distance() -> None:
+    game = SatellitesGame(headless=True)
+    game.add_unit(4, 5, 0, "bot", 1)
+    cell = game.coord_to_cell_id
+
+    assert game.reachable_artefacts(0, 1) == [cell[(4, 4)]]
+    assert game.reachable_artefacts(1, 1) == []
+    assert game.reachable_artefacts(0, 20) == sorted(cell[a] for a in game.artefacts)
+
+
+def test_repr_draws_staggered_board_and_status() -> None:
+    game = SatellitesGame(headless=True)
+    game.add_unit(4, 5, 1, "bot", 12)
+
+    lines = repr(game).splitlines()
+
+    assert len(lines) == 10
+    assert lines[0] == "        .   .   .   B2  T2  .   .   ."
+    assert lines[4].split() == [".", ".", ".", ".", "*", "b12", ".", "*", ".", ".", ".", "."]
+    assert lines[8].split()[3:5] == ["b2", "t2"]
+    assert lines[9] == "turn 0 | scores 0-0 | CHOOSE_SATELLITE | active None"
+
+
+def test_try_phase_actions_enforce_phase() -> None:
+    game = SatellitesGame(headless=True)
+    empty = next(i for i, sat in enumerate(game.satellites) if sat["charges"] == 0)
+    charged = next(i for i, sat in enumerate(game.satellites) if sat["charges"] > 0)
+
+    assert not game.try_set_direction(True)
+    assert not game.try_select_satellite(empty)
+    assert not game.try_select_satellite(len(game.satellites))
+    assert game.try_select_satellite(charged)
+    assert not game.try_select_satellite(charged)
+    assert game.try_set_direction(False)
+    assert game.state != "CHOOSE_DIRECTION"
+
+
+def test_cell_neighbors_match_hex_neighbors() -> None:
+    game = SatellitesGame(headless=True)
+    adjacency = game.cell_neighbors()
+    cid = game.coord_to_cell_id[(4, 5)]
+
+    assert len(adjacency) == game.num_cells
+    assert sorted(adjacency[cid]) == sorted(game.coord_to_cell_id[n] for n in game.get_hex_neighbors(4, 5))
+    assert all(a in adjacency[b] for a in range(game.num_cells) for b in adjacency[a])
+    assert game.num_edges() == sum(len(nbrs) for nbrs in adjacency)
+
+
+def test_cell_id_and_coords_roundtrip() -> None:
+    game = SatellitesGame(headless=True)
+
+    assert game.cell_id(0, 0) == 0
+    assert game.cell_id(1, 0) == 8
+    assert all(game.cell_id(*game.cell_coords(cid)) == cid for cid in range(game.num_cells))
+    with pytest.raises(ValueError):
+        game.cell_id(0, 8)
+    with pytest.raises(ValueError):
+        game.cell_coords(-1)
+
+
+def test_win_score_is_configurable() -> None:
+    game = SatellitesGame(headless=True)
+    game.WIN_SCORE = 2
+    game.grid = {(4, 5): {"owner": 0, "type": "bot", "count": 2}}
+    _prep_move(game, "bot", 2)
+
+    assert game.try_move((4, 5), (4, 4), 2)
+    assert (game.winner, game.end_reason) == (0, "score_limit")
+    assert SatellitesGame.from_bytes(game.to_bytes()).WIN_SCORE == 2
+
+
+def test_has_any_legal_action_respects_move_amount_limit() -> None:
+    game = SatellitesGame(headless=True)
+    assert game.has_any_legal_action()
+
+    game.grid = {
+        (4, 5): {"owner": 0, "type": "tank", "count": 3},
+    }
+    for coord in game.get_hex_neighbors(4, 5):
+        game.grid[coord] = {"owner": 1, "type": "tank", "count": 2}
+    _prep_move(game, "tank")
+    assert game.has_any_legal_action()
+    assert game.has_any_legal_action(max_move_amount=3)
+    assert not game.has_any_legal_action(max_move_amount=2)
+
+    game.state = "GAME_OVER"
+    assert not game.has_any_legal_action()
+
+
+def test_game_result_pairs_winner_with_reason_code() -> None:
+    game = SatellitesGame(headless=True)
+    assert game.game_result() is None
+
+    game.MAX_TURNS = 1
+    _finish_turn(game)
+
+    assert game.game_result() == (-1, SatellitesGame.END_REASONS.index("turn_limit"))
+
+
+def test_validate_names_first_bad_cell() -> None:
+    game = SatellitesGame(headless=True)
+    game.validate()
+
+    game.grid[(4, 5)] = {"owner": 0, "type": "bot", "count": 0}
+    with pytest.raises(InvalidStateError, match=r"empty stack at \(4, 5\)"):
+        game.validate()
+    game.grid[(4, 5)] = {"owner": 2, "type": "bot", "count": 1}
+    with pytest.raises(InvalidStateError, match=r"owner 2 at \(4, 5\)"):
+        game.validate()
+    del game.grid[(4, 5)]
+    game.add_unit(8, 5, 0, "bot", 1)
+    game.validate()
+    game.grid[(8, 3)] = {"owner": 0, "type": "bot", "count": 2}
+    with pytest.raises(ValueError, match=r"opponent start cell \(8, 3\)"):
+        game.validate()
+
+
+def test_mirror_is_an_involution_that_preserves_legality() -> None:
+    game = SatellitesGame(headless=True, seed=1)
+    game.add_unit(3, 2, 0, "bot", 3)
+    _prep_move(game, "bot")
+
+    mirrored = game.mirror()
+
+    assert mirrored.grid[(3, 8)] == {"owner": 0, "type": "bot", "count": 3}
+    assert sorted(mirrored.artefacts) == sorted(game.artefacts)
+    assert mirrored.mirror().to_bytes() == game.to_bytes()
+    assert sorted(mirrored.legal_actions()) == sorted(game.mirror_action(a) for a in game.legal_actions())
+
+
+def test_scored_moves_report_artefact_points() -> None:
+    game = SatellitesGame(headless=True)
+    game.grid = {(4, 5): {"owner": 0, "type": "bot", "count": 2}}
+    _prep_move(game, "bot")
+
+    scored = {(s, e, a): d for s, e, a, d in game.scored_moves()}
+
+    assert set(scored) == set(game.legal_moves())
+    assert scored[((4, 5), (4, 4), 2)] == 2
+    assert scored[((4, 5), (4, 4), 1)] == 1
+    assert scored[((4, 5), (4, 6), 2)] == 0
+    assert all(d == 0 for s, e, a, d in game.scored_moves() if e != (4, 4))
+
+
+def test_material_splits_units_by_owner_and_kind() -> None:
+    game = SatellitesGame(headless=True)
+    game.add_unit(4, 5, 1, "tank", 3)
+
+    assert game.material() == (2, 2, 2, 5)
+    assert (game.supply_used(0), game.supply_used(1)) == (4, 7)
+
+
+def test_bfs_distances_grow_one_hop_per_ring() -> None:
+    game = SatellitesGame(headless=True)
+    start = game.cell_id(4, 5)
+    dist = game.bfs_distances(start)
+    adjacency = game.cell_neighbors()
+
+    assert dist[start] == 0
+    assert all(dist[n] == 1 for n in adjacency[start])
+    assert all(abs(dist[a] - dist[b]) <= 1 for a in range(game.num_cells) for b in adjacency[a])
+    assert min(dist) == 0 and -1 not in dist
+    with pytest.raises(ValueError):
+        game.bfs_distances(game.num_cells)
+
+
+def test_threatened_cells_follow_tank_attack_rules() -> None:
+    game = SatellitesGame(headless=True)
+    game.grid = {
+        (4, 5): {"owner": 1, "type": "tank", "count": 2},
+        (4, 6): {"owner": 0, "type": "tank", "count": 2},
+        (3, 5): {"owner": 0, "type": "bot", "count": 5},
+        (5, 5): {"owner": 1, "type": "bot", "count": 1},
+    }
+    before = game.to_bytes()
+
+    threats = game.threatened_cells(1)
+
+    cell = game.coord_to_cell_id
+    assert threats[cell[(3, 5)]]
+    assert not threats[cell[(4, 6)]]
+    assert not threats[cell[(5, 5)]]
+    assert not threats[cell[(4, 4)]]
+    assert threats[cell[(5, 4)]]
+    assert game.to_bytes() == before
+
+
+def _cache_snapshot(game: SatellitesGame):
+    game._ensure_cache()
+    return (
+        game.unit_owner.copy(),
+        game.unit_kind.copy(),
+        game.unit_count.copy(),
+        game.owner_total_units.copy(),
+        [cells.copy() for cells in game.owner_bot_cells],
+        [cells.copy() for cells in game.owner_tank_cells],
+    )
+
+
+def test_incremental_cell_cache_matches_full_rebuild() -> None:
+    game = SatellitesGame(headless=True, seed=4)
+    game.MAX_TURNS = 12
+    for step in range(300):
+        actions = game.legal_actions()
+        if not actions:
+            break
+        ok, token, _ = game.apply_action_with_undo(actions[step * 7 % len(actions)])
+        incremental = _cache_snapshot(game)
+        game._cache_dirty = True
+        assert _cache_snapshot(game) == incremental
+        if step % 5 == 0:
+            game.undo_action(token)
+            incremental = _cache_snapshot(game)
+            game._cache_dirty = True
+            assert _cache_snapshot(game) == incremental
+            game.apply_action(actions[step * 7 % len(actions)])
+
+
+def test_refresh_cells_asserts_on_drifted_totals() -> None:
+    game = SatellitesGame(headless=True)
+    game.add_unit(4, 5, 0, "bot", 2)
+    game._ensure_cache()
+    game.owner_total_units[0] += 1
+
+    with pytest.raises(AssertionError, match="patched totals"):
+        game._refresh_cells([(4, 5)])
+
+
+def test_with_action_returns_child_and_keeps_parent() -> None:
+    game = SatellitesGame(headless=True)
+    before = game.to_bytes()
+    action = game.legal_actions()[0]
+
+    child, ok = game.with_action(action)
+
+    assert ok and child.state == "CHOOSE_DIRECTION"
+    assert game.to_bytes() == before
+    assert game.with_action(("add", 4, 5))[1] is False
+
+
+def test_phase_and_action_type_enums_track_state() -> None:
+    game = SatellitesGame(headless=True)
+    assert (game.phase(), game.action_type_enum()) == (Phase.CHOOSE_SATELLITE, ActionType.NONE)
+
+    idx = next(i for i, sat in enumerate(game.satellites) if sat["type"] == "move_tank")
+    game.apply_action(("select_satellite", idx))
+    assert (game.phase(), game.action_type_enum()) == (Phase.CHOOSE_DIRECTION, ActionType.MOVE_TANK)
+    assert game.phase() == 2 and ActionType.ADD_BOT == 2
+
+
+def test_sample_legal_action_is_seeded_and_covers_all_actions() -> None:
+    game = SatellitesGame(headless=True)
+    legal = game.legal_actions()
+
+    picks = [game.sample_legal_action(seed=s) for s in range(200)]
+
+    assert picks == [game.sample_legal_action(seed=s) for s in range(200)]
+    assert set(picks) == set(legal)
+    game.state = "GAME_OVER"
+    assert game.sample_legal_action(seed=0) is None
+
+
+def test_legal_action_count_matches_legal_actions_each_phase() -> None:
+    game = SatellitesGame(headless=True, seed=6)
+    game.MAX_TURNS = 4
+    while game.state != "GAME_OVER":
+        actions = game.legal_actions()
+        assert game.legal_action_count() == len(actions)
+        assert game.legal_action_count(max_move_amount=1) == len(
+            [a for a in actions if a[0] != "move" or a[3] <= 1]
+        )
+        game.apply_action(actions[-1])
+    assert game.legal_action_count() == 0
+
+
+def test_bitboards_partition_cells() -> None:
+    game = SatellitesGame(headless=True)
+    boards = game.bitboards()
+    cell = game.coord_to_cell_id
+
+    assert boards["p0"] == (1 << cell[(0, 3)]) | (1 << cell[(0, 4)])
+    assert boards["tanks"] == (1 << cell[(0, 4)]) | (1 << cell[(8, 4)])
+    assert bin(boards["artefacts"]).count("1") == 6
+    assert boards["p0"] | boards["p1"] | boards["empty"] == (1 << game.num_cells) - 1
+    assert boards["bots"] | boards["tanks"] == boards["p0"] | boards["p1"]
+    assert boards["empty"] & boards["p0"] == 0
+
+
+def test_charge_totals_follow_pickup_and_distribution() -> None:
+    game = SatellitesGame(headless=True)
+    assert (game.total_charges(), game.charged_satellites()) == (8, 4)
+
+    game.apply_action(game.legal_actions()[0])
+    assert (game.total_charges(), game.charged_satellites()) == (6, 3)
+
+    game.apply_action(("set_direction", True))
+    assert game.total_charges() == 8
+
+
+def test_satellites_of_type_by_name_and_code() -> None:
+    game = SatellitesGame(headless=True)
+
+    move_tank = game.satellites_of_type("move_tank")
+    assert len(move_tank) == 2
+    assert all(game.satellites[i]["type"] == "move_tank" for i in move_tank)
+    assert game.satellites_of_type(0) == move_tank
+    assert len(game.satellites_of_type(2)) == 1
+    assert game.satellites_of_type("add_tank", charged_only=True) == []
+
+
+def _set_artefacts(game: SatellitesGame, coords) -> None:
+    game.artefacts = list(coords)
+    game.is_artefact_cell = [False] * game.num_cells
+    for coord in coords:
+        game.is_artefact_cell[game.coord_to_cell_id[coord]] = True
+
+
+def _boxed_in_game() -> SatellitesGame:
+    game = SatellitesGame(headless=True)
+    game.grid = {
+        (0, 0): {"owner": 0, "type": "tank", "count": 20},
+        (8, 7): {"owner": 1, "type": "tank", "count": 20},
+    }
+    _set_artefacts(game, [(0, 1), (1, 0), (1, 1), (8, 6), (7, 7), (7, 8)])
+    return game
+
+
+def test_is_stalemate_when_both_sides_capped_and_boxed_in() -> None:
+    game = _boxed_in_game()
+
+    assert game.is_stalemate() is True
+
+    _set_artefacts(game, [(0, 1), (1, 0), (8, 6), (7, 7), (7, 8)])
+    assert game.is_stalemate() is False
+
+
+def test_opening_position_is_not_stalemate() -> None:
+    assert SatellitesGame(headless=True).is_stalemate() is False
+
+
+def test_try_move_respects_active_satellite_type() -> None:
+    game = SatellitesGame(headless=True)
+    game.grid = {
+        (4, 5): {"owner": 0, "type": "tank", "count": 2},
+        (2, 2): {"owner": 0, "type": "bot", "count": 1},
+    }
+    _prep_move(game, "bot", 2)
+
+    assert game.try_move((4, 5), (4, 6), 1) is False
+    assert game.grid[(4, 5)]["count"] == 2
+    assert game.try_move((2, 2), (2, 3), 1) is True
+    assert game.grid[(2, 3)] == {"owner": 0, "type": "bot", "count": 1}
+
+
+def test_try_add_respects_active_satellite_type() -> None:
+    game = SatellitesGame(headless=True)
+    _prep_add_tank(game, turn=0)
+
+    assert game.try_add(4, 4) is False
+    assert game.try_add(4, 5) is True
+    assert game.grid[(4, 5)] == {"owner": 0, "type": "tank", "count": 1}
+
+    game.state = "CHOOSE_SATELLITE"
+    assert game.try_add(4, 6) is False
+
+
+def test_two_consecutive_forced_passes_end_the_game() -> None:
+    game = _boxed_in_game()
+    game.scores = [1, 2]
+
+    _finish_turn(game, actions_taken=0)
+    assert game.state == "CHOOSE_SATELLITE"
+    _finish_turn(game, actions_taken=0)
+
+    assert game.state == "GAME_OVER"
+    assert game.end_reason == "both_passed"
+    assert game.winner == 1
+
+
+def test_voluntary_empty_turns_are_not_passes() -> None:
+    game = SatellitesGame(headless=True)
+
+    _finish_turn(game, actions_taken=0)
+    _finish_turn(game, actions_taken=0)
+
+    assert game.state == "CHOOSE_SATELLITE"
+    assert game.consecutive_passes == 0
+
+
+def test_an_active_turn_resets_the_pass_counter() -> None:
+    game = _boxed_in_game()
+
+    _finish_turn(game, actions_taken=0)
+    _finish_turn(game, actions_taken=2)
+    _finish_turn(game, actions_taken=0)
+
+    assert game.state == "CHOOSE_SATELLITE"
+    assert game.consecutive_passes == 1
+
+
+def test_move_counts_towards_turn_actions() -> None:
+    game = SatellitesGame(headless=True)
+    game.grid = {(4, 5): {"owner": 0, "type": "tank", "count": 2}}
+    _prep_move(game, "tank", 2)
+
+    game.apply_action(("move", (4, 5), (4, 6), 1))
+
+    assert game.turn_actions_taken == 1
+
+
+def _tied_artefact_ending(tie_seed: int) -> SatellitesGame:
+    game = SatellitesGame(headless=True)
+    game.TIE_POLICY = "random"
+    game.tie_seed = tie_seed
+    game.scores = [3, 3]
+    game.artefacts = []
+    return game
+
+
+def test_random_tie_policy_is_reproducible_per_seed() -> None:
+    winners = set()
+    for seed in range(16):
+        first = _tied_artefact_ending(seed)
+        second = first.clone()
+        assert first.check_win() is True and second.check_win() is True
+        assert first.winner == second.winner
+        winners.add(first.winner)
+
+    assert winners == {0, 1}
+
+
+def test_random_tie_policy_handles_unserializable_scores() -> None:
+    game = _tied_artefact_ending(5)
+    game.UNIT_CAP = 0
+    game.WIN_SCORE = 1000
+    game.scores = [300, 300]
+
+    assert game.check_win() is True
+    assert game.end_reason == "artefacts_exhausted"
+    assert game.winner in (0, 1)
+    with pytest.raises(ValueError):
+        game.tie_seed = -1
+
+
+def test_random_tie_policy_applies_to_early_endings() -> None:
+    game = _tied_artefact_ending(3)
+    game.artefacts = [(4, 4)]
+    game.MAX_TURNS = 1
+    replay = game.clone()
+
+    _finish_turn(game)
+    _finish_turn(replay)
+
+    assert game.end_reason == "turn_limit"
+    assert game.winner in (0, 1)
+    assert game.winner == replay.winner
@@ -0,0 +1,212 @@
+import struct
+import zlib
+
+import pytest
+
+from engine import InvalidStateError, SatellitesGame, StateFormatError, game_diff
+
+
+def _played_game() -> SatellitesGame:
+    game = SatellitesGame(headless=True)
+    game.scores = [3, 5]
+    game.artefacts.remove((4, 4))
+    game.is_artefact_cell[game.coord_to_cell_id[(4, 4)]] = False
+    game.apply_action(game.legal_actions()[0])
+    game.apply_action(("set_direction", True))
+    return game
+
+
+def test_bytes_roundtrip_preserves_rules_state() -> None:
+    game = _played_game()
+
+    restored = SatellitesGame.from_bytes(game.to_bytes())
+
+    assert restored.grid == game.grid
+    assert restored.artefacts == game.artefacts
+    assert restored.is_artefact_cell == game.is_artefact_cell
+    assert restored.satellites == game.satellites
+    assert restored.scores == game.scores
+    assert restored.turn == game.turn
+    assert restored.state == game.state
+    assert restored.active_satellite_idx == game.active_satellite_idx
+    assert restored.actions_remaining == game.actions_remaining
+    assert restored.action_type == game.action_type
+    assert restored.distribution_direction == game.distribution_direction
+    assert restored.halfmove_clock == game.halfmove_clock
+    assert restored.ply == game.ply
+    assert restored.legal_actions() == game.legal_actions()
+    assert restored.to_bytes() == game.to_bytes()
+
+
+def test_bytes_roundtrip_preserves_custom_start_cells() -> None:
+    game = SatellitesGame(headless=True)
+    game.is_p0_start_cell[game.coord_to_cell_id[(0, 3)]] = False
+    game.is_p0_start_cell[game.coord_to_cell_id[(1, 1)]] = True
+    game.is_p1_start_cell[game.coord_to_cell_id[(7, 7)]] = True
+
+    restored = SatellitesGame.from_bytes(game.to_bytes())
+
+    assert restored.is_p0_start_cell == game.is_p0_start_cell
+    assert restored.is_p1_start_cell == game.is_p1_start_cell
+    assert restored.start_cells(0) == [(0, 4), (1, 1)]
+
+
+def test_from_bytes_rejects_wrong_version() -> None:
+    data = bytearray(SatellitesGame(headless=True).to_bytes())
+    data[0] = SatellitesGame.FORMAT_VERSION + 1
+
+    with pytest.raises(StateFormatError, match="version"):
+        SatellitesGame.from_bytes(bytes(data))
+
+
+def test_from_bytes_rejects_unknown_codes_with_valid_checksum() -> None:
+    data = bytearray(SatellitesGame(headless=True).to_bytes()[:-4])
+    data[6] = 9  # state code, after the version byte, both scores and the turn
+    data += struct.pack(">I", zlib.crc32(bytes(data)))
+
+    with pytest.raises(StateFormatError, match="state code 9"):
+        SatellitesGame.from_bytes(bytes(data))
+
+
+def test_from_bytes_rejects_corrupted_payload() -> None:
+    data = bytearray(SatellitesGame(headless=True).to_bytes())
+    data[10] ^= 0x01
+
+    with pytest.raises(StateFormatError, match="checksum"):
+        SatellitesGame.from_bytes(bytes(data))
+
+
+def test_fingerprint_is_stable_and_state_sensitive() -> None:
+    game = _played_game()
+    fp = game.fingerprint()
+
+    assert len(fp) == 16
+    assert int(fp, 16) >= 0
+    assert game.clone().fingerprint() == fp
+    assert SatellitesGame.from_bytes(game.to_bytes()).fingerprint() == fp
+
+    game.scores[1] += 1
+    assert game.fingerprint() != fp
+
+
+def test_game_diff_reports_changed_cells_and_fields() -> None:
+    game = SatellitesGame(headless=True)
+    other = game.clone()
+    assert game_diff(game, other) == ([], {})
+
+    other.add_unit(4, 4, 1, "bot", 3)
+    other.scores[1] = 2
+    cid = other.coord_to_cell_id[(4, 4)]
+    cells, fields = game_diff(game, other)
+    assert cells == [(cid, 1, 1, 3)]
+    assert fields == {"scores": ([0, 0], [0, 2])}
+
+
+def test_bytes_roundtrip_preserves_artefact_values() -> None:
+    game = SatellitesGame(headless=True, artefact_values={(2, 1): 4})
+
+    restored = SatellitesGame.from_bytes(game.to_bytes())
+
+    assert restored.artefact_value == game.artefact_value
+    assert game_diff(game, restored) == ([], {})
+
+
+def test_bytes_roundtrip_handles_wide_scores_and_artefact_values() -> None:
+    game = SatellitesGame(headless=True, artefact_values={(2, 1): 1000, (4, 4): -3})
+    game.UNIT_CAP = 0
+    game.WIN_SCORE = 200
+    game.scores = [300, 299]
+
+    restored = SatellitesGame.from_bytes(game.to_bytes())
+
+    assert restored.scores == [300, 299]
+    assert restored.artefact_value == game.artefact_value
+    with pytest.raises(ValueError):
+        SatellitesGame(headless=True, artefact_values={(2, 1): 1.5})
+    with pytest.raises(ValueError):
+        SatellitesGame(headless=True, artefact_values={(2, 1): 40000})
+
+
+def test_to_bytes_checks_rule_setting_ranges() -> None:
+    game = SatellitesGame(headless=True)
+    game.WIN_SCORE = 1000
+
+    assert SatellitesGame.from_bytes(game.to_bytes()).WIN_SCORE == 1000
+    game.MAX_TURNS = 100000
+    with pytest.raises(ValueError, match="MAX_TURNS"):
+        game.to_bytes()
+    game.MAX_TURNS = 100
+    game.MAX_HALFMOVE_CLOCK = 0xFFFF
+    with pytest.raises(ValueError, match="MAX_HALFMOVE_CLOCK"):
+        game.to_bytes()
+
+
+def test_bytes_roundtrip_after_negative_artefact_capture() -> None:
+    game = SatellitesGame(headless=True, artefact_values={(4, 4): -3})
+    game.grid = {(4, 5): {"owner": 0, "type": "bot", "count": 2}}
+    game.turn = 0
+    game.state = "PERFORM_ACTIONS"
+    game.action_type = "move_bot"
+    game.actions_remaining = 2
+    assert game.try_move((4, 5), (4, 4), 2)
+    assert game.scores == [-6, 0]
+
+    restored = SatellitesGame.from_bytes(game.to_bytes())
+
+    assert restored.scores == [-6, 0]
+    assert game_diff(game, restored) == ([], {})
+
+
+def test_from_bytes_rejects_rule_violations() -> None:
+    game = SatellitesGame(headless=True)
+    game.add_unit(4, 4, 0, "tank", 1)
+
+    with pytest.raises(InvalidStateError, match=r"uncaptured artefact \(4, 4\)"):
+        SatellitesGame.from_bytes(game.to_bytes())
+
+
+def test_zobrist_hash_tracks_gameplay_state() -> None:
+    game = _played_game()
+    h = game.zobrist_hash()
+
+    assert game.clone().zobrist_hash() == h
+    assert SatellitesGame.from_bytes(game.to_bytes()).zobrist_hash() == h
+    game.info_message = "ignored"
+    assert game.zobrist_hash() == h
+
+    game.add_unit(4, 5, 0, "bot", 1)
+    assert game.zobrist_hash() != h
+
+
+def test_zobrist_hash_covers_picked_up_charges_and_direction() -> None:
+    game = _played_game()
+    h = game.zobrist_hash()
+
+    charged = game.clone()
+    charged.picked_up_charges += 1
+    assert charged.zobrist_hash() != h
+
+    turned = game.clone()
+    turned.distribution_direction = -game.distribution_direction
+    assert turned.zobrist_hash() != h
+
+
+def test_bytes_roundtrip_has_no_diff_through_a_game() -> None:
+    game = SatellitesGame(headless=True, seed=5)
+    game.MAX_TURNS = 8
+    game.MAX_HALFMOVE_CLOCK = 30
+    game.WIN_SCORE = 12
+    game.TIE_POLICY = "random"
+    game.tie_seed = 77
+    game.is_p0_start_cell[game.coord_to_cell_id[(1, 1)]] = True
+    game.is_p1_start_cell[game.coord_to_cell_id[(7, 7)]] = True
+    for _ in range(200):
+        restored = SatellitesGame.from_bytes(game.to_bytes())
+        assert game_diff(game, restored) == ([], {})
+        assert restored.start_cells(0) == game.start_cells(0)
+        assert restored.start_cells(1) == game.start_cells(1)
+        assert restored.zobrist_hash() == game.zobrist_hash()
+        actions = game.legal_actions()
+        if not actions:
+            break
+        game.apply_action(actions[len(actions) // 2])
@@ -0,0 +1,140 @@
+import pytest
+
+from agents.playout import (
+    action_priors,
+    evaluate_turn,
+    finish_turn_greedy,
+    greedy_action,
+    material_eval,
+    playout_greedy,
+    playout_random,
+    playout_random_batch,
+    positional_eval,
+)
+from engine import SatellitesGame
+
+
+def _capture_game(perform: bool = True) -> SatellitesGame:
+    game = SatellitesGame(headless=True)
+    game.grid = {
+        (4, 5): {"owner": 0, "type": "tank", "count": 2},
+        (4, 6): {"owner": 1, "type": "bot", "count": 3},
+    }
+    game.turn = 0
+    if perform:
+        game.state = "PERFORM_ACTIONS"
+        game.action_type = "move_tank"
+        game.actions_remaining = 2
+    return game
+
+
+def test_material_eval_counts_units_and_score() -> None:
+    game = SatellitesGame(headless=True)
+    assert material_eval(game, 0) == 0.0
+
+    game.scores = [2, 0]
+    game.grid = {(4, 5): {"owner": 0, "type": "tank", "count": 3}}
+    assert material_eval(game, 0) == 9.0
+    assert material_eval(game, 1) == -9.0
+
+
+def test_greedy_action_prefers_capture() -> None:
+    game = _capture_game()
+    before = game.clone()
+
+    action = greedy_action(game)
+
+    assert action[0] == "move" and action[2] == (4, 6)
+    assert game.grid == before.grid
+
+
+def test_action_priors_report_material_delta_per_action() -> None:
+    game = _capture_game()
+
+    priors = dict(action_priors(game))
+
+    assert set(priors) == set(game.legal_actions())
+    assert priors[("move", (4, 5), (4, 6), 1)] == 3.0
+    assert priors[("move", (4, 5), (3, 5), 1)] == 0.0
+
+
+def test_playout_greedy_is_reproducible_and_leaves_input_untouched() -> None:
+    game = SatellitesGame(headless=True)
+    game.MAX_TURNS = 6
+    before = game.clone()
+
+    first = playout_greedy(game, seed=7, greediness=0.5)
+    second = playout_greedy(game, seed=7, greediness=0.5)
+
+    assert first == second
+    assert first in (-1, 0, 1)
+    assert game.grid == before.grid
+    assert game.state == before.state
+
+
+def test_finish_turn_greedy_spends_remaining_charges() -> None:
+    game = _capture_game()
+    game.add_unit(8, 4, 1, "tank", 1)
+
+    applied = finish_turn_greedy(game)
+
+    assert len(applied) == 2
+    assert applied[0] == ("move", (4, 5), (4, 6), 1)
+    assert game.turn == 1
+    assert game.state == "CHOOSE_SATELLITE"
+    assert finish_turn_greedy(game) == []
+
+
+def test_evaluate_turn_scores_plan_without_mutating() -> None:
+    game = _capture_game(perform=False)
+    sat = next(i for i, s in enumerate(game.satellites) if s["type"] == "move_tank")
+    game.satellites[sat]["charges"] = 1
+    before = game.clone()
+
+    capture = [("move", (4, 5), (4, 6), 1)]
+    assert evaluate_turn(game, sat, True, capture, 0) == 2.0
+    assert evaluate_turn(game, sat, True, capture, 1) == -2.0
+    assert game.grid == before.grid
+    with pytest.raises(ValueError):
+        evaluate_turn(game, sat, True, [("move", (4, 5), (6, 5), 1)], 0)
+
+
+def test_playout_random_is_reproducible_and_capped() -> None:
+    game = SatellitesGame(headless=True)
+    game.MAX_TURNS = 6
+    before = game.clone()
+
+    results = {playout_random(game, seed=3) for _ in range(2)}
+
+    assert len(results) == 1 and results.pop() in (-1, 0, 1)
+    assert game.grid == before.grid
+    assert playout_random(SatellitesGame(headless=True), seed=3, max_steps=2) is None
+
+
+def test_positional_eval_is_symmetric_and_bounded() -> None:
+    game = SatellitesGame(headless=True)
+    assert positional_eval(game, 0) == 0.0
+
+    game.add_unit(4, 5, 0, "bot", 1)
+    ahead = positional_eval(game, 0)
+    assert 0.0 < ahead < 1.0
+    assert positional_eval(game, 1) == -ahead
+    assert positional_eval(game, 0, proximity_weight=0.0) < ahead
+
+    game.state = "GAME_OVER"
+    game.winner = 1
+    assert (positional_eval(game, 0), positional_eval(game, 1)) == (-1.0, 1.0)
+
+
+def test_playout_random_batch_matches_serial_playouts() -> None:
+    games = [SatellitesGame(headless=True, seed=i) for i in range(3)]
+    for game in games:
+        game.MAX_TURNS = 5
+    seeds = [11, 12, 13]
+
+    expected = [playout_random(g, seed=s) for g, s in zip(games, seeds)]
+
+    assert playout_random_batch(games, seeds) == expected
+    assert playout_random_batch(games, seeds, workers=2) == expected
+    with pytest.raises(ValueError):
+        playout_random_batch(games, seeds[:2])
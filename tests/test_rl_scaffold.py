@@ -58,3 +58,17 @@ def test_alpha_mcts_and_selfplay_smoke() -> None:
     assert examples[0].policy.shape == (action_space.size,)
     assert -1.0 <= examples[0].value <= 1.0
 
+
+
+def test_alpha_mcts_select_action_repeats_for_equal_seeds() -> None:
+    game = SatellitesGame(headless=True, seed=2)
+    action_space = GlobalActionSpace(game)
+    enc = FeatureEncoder(game)
+    torch.manual_seed(0)
+    model = SatellitesPolicyValueNet(enc.feature_dim, action_space.size)
+
+    picks = [
+        AlphaMCTS(model, action_space, enc, simulations=8, seed=4).select_action(game)[0]
+        for _ in range(2)
+    ]
+    assert picks[0] == picks[1]
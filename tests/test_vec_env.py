@@ -0,0 +1,64 @@
+import numpy as np
+import pytest
+
+from rl.vec_env import VecEnv
+
+
+def _first_legal(env: VecEnv) -> list:
+    return [int(np.flatnonzero(mask)[0]) if mask.any() else 0 for mask in env.legal_action_masks()]
+
+
+def test_step_full_reports_rewards_dones_and_winners() -> None:
+    env = VecEnv(2)
+    env.games[1].scores[0] = 9
+    env.games[1].check_win()
+
+    rewards, dones, winners = env.step_full(_first_legal(env))
+
+    assert rewards.dtype == np.float32 and rewards.shape == (2,)
+    assert dones.tolist() == [False, True]
+    assert rewards.tolist() == [0.0, 0.0]
+    assert winners == [None, 0]
+
+
+def test_step_full_rewards_the_winning_actor() -> None:
+    env = VecEnv(1)
+    game = env.games[0]
+    game.grid = {(3, 1): {"owner": 0, "type": "bot", "count": 2}}
+    game.scores = [8, 0]
+    game.turn = 0
+    game.state = "PERFORM_ACTIONS"
+    game.action_type = "move_bot"
+    game.actions_remaining = 1
+    capture = env.action_space.to_index(("move", (3, 1), (2, 1), 1))
+
+    rewards, dones, winners = env.step_full([capture])
+
+    assert rewards.tolist() == [1.0]
+    assert dones.tolist() == [True]
+    assert winners == [0]
+
+
+def test_auto_reset_replaces_finished_games_after_reporting_done() -> None:
+    env = VecEnv(2, auto_reset=True)
+    env.games[1].scores[0] = 9
+    env.games[1].check_win()
+
+    _, dones, winners = env.step_full(_first_legal(env))
+
+    assert dones.tolist() == [False, True]
+    assert winners == [None, 0]
+    assert env.games[1].state == "CHOOSE_SATELLITE"
+    assert env.games[1].scores == [0, 0]
+
+
+def test_step_full_rejects_illegal_index_before_stepping_any_env() -> None:
+    env = VecEnv(2)
+    before = [g.to_bytes() for g in env.games]
+    legal = _first_legal(env)[0]
+    wrong_phase = env.action_space.move_index((0, 0), (0, 1), 1)
+
+    with pytest.raises(ValueError, match="env 1"):
+        env.step_full([legal, wrong_phase])
+
+    assert [g.to_bytes() for g in env.games] == before